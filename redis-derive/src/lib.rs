@@ -0,0 +1,183 @@
+//! Derive macros for the `redis` crate.
+//!
+//! `#[derive(FromRedisValue)]` implements `redis::FromRedisValue` for structs
+//! with named fields, reading them from a map reply such as the flat list of
+//! alternating field names and values returned by `HGETALL`, or a RESP3 map.
+//!
+//! # Example
+//!
+//! ```rust
+//! use redis::{FromRedisValue, Value};
+//! use redis_derive::FromRedisValue;
+//!
+//! #[derive(FromRedisValue)]
+//! struct User {
+//!     name: String,
+//!     #[redis(rename = "e-mail")]
+//!     email: String,
+//!     age: Option<u32>,
+//! }
+//!
+//! let reply = Value::Bulk(vec![
+//!     Value::Data(b"name".to_vec()),
+//!     Value::Data(b"Ada".to_vec()),
+//!     Value::Data(b"e-mail".to_vec()),
+//!     Value::Data(b"ada@example.com".to_vec()),
+//! ]);
+//! let user = User::from_redis_value(&reply).unwrap();
+//! assert_eq!(user.name, "Ada");
+//! assert_eq!(user.email, "ada@example.com");
+//! assert_eq!(user.age, None);
+//! ```
+//!
+//! Each field is read from the entry named like it, or like its
+//! `#[redis(rename = "...")]` attribute. Entries without a matching field
+//! are ignored. A missing entry is an error, unless the field is an
+//! `Option`, in which case it is `None`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Field, Fields, GenericParam, Lit, Meta,
+    NestedMeta, Type,
+};
+
+/// Implements `redis::FromRedisValue` for a struct with named fields, see the
+/// [crate documentation](crate).
+#[proc_macro_derive(FromRedisValue, attributes(redis))]
+pub fn derive_from_redis_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_redis_value(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand_from_redis_value(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "FromRedisValue can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "FromRedisValue can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut declarations = Vec::with_capacity(fields.len());
+    let mut arms = Vec::with_capacity(fields.len());
+    let mut initializers = Vec::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let var = format_ident!("__field{}", i);
+        let name = field_name(field)?;
+
+        declarations.push(quote! {
+            let mut #var: ::std::option::Option<#ty> = ::std::option::Option::None;
+        });
+        arms.push(quote! {
+            #name => #var = ::std::option::Option::Some(::redis::from_redis_value(value)?),
+        });
+        initializers.push(if is_option(ty) {
+            quote! { #ident: #var.unwrap_or_default() }
+        } else {
+            quote! {
+                #ident: #var.ok_or_else(|| {
+                    ::redis::RedisError::from((
+                        ::redis::ErrorKind::TypeError,
+                        "Missing field",
+                        #name.to_string(),
+                    ))
+                })?
+            }
+        });
+    }
+
+    // Type parameters are read from the reply too, so they need to be convertible.
+    let mut generics = input.generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(ref mut param) = *param {
+            param.bounds.push(parse_quote!(::redis::FromRedisValue));
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::redis::FromRedisValue for #ident #ty_generics #where_clause {
+            fn from_redis_value(v: &::redis::Value) -> ::redis::RedisResult<Self> {
+                let entries = v.as_map_iter().ok_or_else(|| {
+                    ::redis::RedisError::from((
+                        ::redis::ErrorKind::TypeError,
+                        "Response type not convertible",
+                        ::std::format!("Expected a map, got {:?}", v),
+                    ))
+                })?;
+                #(#declarations)*
+                for (key, value) in entries {
+                    let key: ::std::string::String = ::redis::from_redis_value(key)?;
+                    match key.as_str() {
+                        #(#arms)*
+                        _ => {}
+                    }
+                }
+                ::std::result::Result::Ok(#ident {
+                    #(#initializers,)*
+                })
+            }
+        }
+    })
+}
+
+// The name of the map entry a field is read from.
+fn field_name(field: &Field) -> syn::Result<String> {
+    let mut name = field.ident.as_ref().unwrap().to_string();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("redis"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected `redis(...)`")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(ref value)) if value.path.is_ident("rename") => {
+                    match value.lit {
+                        Lit::Str(ref lit) => name = lit.value(),
+                        ref lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                    }
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "unsupported redis attribute, expected `rename`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(name)
+}
+
+// Whether a field is optional, that is its type is `Option<T>`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
@@ -0,0 +1,138 @@
+use redis::{ErrorKind, FromRedisValue, Value};
+use redis_derive::FromRedisValue;
+
+#[derive(Debug, PartialEq, FromRedisValue)]
+struct User {
+    id: u64,
+    name: String,
+    #[redis(rename = "e-mail")]
+    email: String,
+    score: f64,
+    nickname: Option<String>,
+}
+
+fn data(s: &str) -> Value {
+    Value::Data(s.as_bytes().to_vec())
+}
+
+#[test]
+fn test_derive_from_flat_list() {
+    let reply = Value::Bulk(vec![
+        data("name"),
+        data("Ada"),
+        data("id"),
+        data("7"),
+        data("e-mail"),
+        data("ada@example.com"),
+        data("score"),
+        data("1.5"),
+        data("nickname"),
+        data("countess"),
+        data("unknown"),
+        data("ignored"),
+    ]);
+
+    assert_eq!(
+        User::from_redis_value(&reply),
+        Ok(User {
+            id: 7,
+            name: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+            score: 1.5,
+            nickname: Some("countess".to_string()),
+        })
+    );
+}
+
+#[test]
+fn test_derive_from_map() {
+    let reply = Value::Map(vec![
+        (data("id"), Value::Int(7)),
+        (data("name"), data("Ada")),
+        (data("e-mail"), data("ada@example.com")),
+        (data("score"), data("1.5")),
+    ]);
+
+    assert_eq!(
+        User::from_redis_value(&reply),
+        Ok(User {
+            id: 7,
+            name: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+            score: 1.5,
+            nickname: None,
+        })
+    );
+}
+
+#[test]
+fn test_derive_errors() {
+    // The field is renamed, so `email` doesn't match it
+    let reply = Value::Bulk(vec![
+        data("id"),
+        data("7"),
+        data("name"),
+        data("Ada"),
+        data("email"),
+        data("ada@example.com"),
+        data("score"),
+        data("1.5"),
+    ]);
+    let err = User::from_redis_value(&reply).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TypeError);
+    assert_eq!(err.detail(), Some("e-mail"));
+
+    let reply = Value::Bulk(vec![data("id"), data("not a number")]);
+    assert_eq!(
+        User::from_redis_value(&reply).unwrap_err().kind(),
+        ErrorKind::TypeError
+    );
+
+    assert_eq!(
+        User::from_redis_value(&Value::Int(1)).unwrap_err().kind(),
+        ErrorKind::TypeError
+    );
+}
+
+#[derive(Debug, PartialEq, FromRedisValue)]
+struct Entry<T> {
+    key: String,
+    value: T,
+}
+
+#[test]
+fn test_derive_generic() {
+    let reply = Value::Bulk(vec![data("key"), data("counter"), data("value"), data("3")]);
+
+    assert_eq!(
+        Entry::<u32>::from_redis_value(&reply),
+        Ok(Entry {
+            key: "counter".to_string(),
+            value: 3,
+        })
+    );
+    assert_eq!(
+        Entry::<Vec<u8>>::from_redis_value(&reply),
+        Ok(Entry {
+            key: "counter".to_string(),
+            value: b"3".to_vec(),
+        })
+    );
+}
+
+// The generated code refers to the `redis` crate, not to a `redis` item in scope.
+mod shadowed {
+    #[allow(dead_code)]
+    mod redis {}
+
+    #[derive(redis_derive::FromRedisValue)]
+    pub struct Point {
+        pub x: i64,
+    }
+}
+
+#[test]
+fn test_derive_with_shadowed_redis() {
+    let reply = Value::Bulk(vec![data("x"), data("-2")]);
+    assert_eq!(shadowed::Point::from_redis_value(&reply).unwrap().x, -2);
+}
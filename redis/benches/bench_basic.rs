@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Bencher, Criterion, Throughput};
 use futures::{prelude::*, stream};
-use redis::{RedisError, Value};
+use redis::{Commands, Encoding, RedisError, Value};
 
 use support::*;
 
@@ -196,6 +196,58 @@ fn bench_query(c: &mut Criterion) {
     group.finish();
 }
 
+const ENCODING_KEYS_PER_TYPE: usize = 10_000;
+const ENCODING_KEY_TYPES: [&str; 6] = ["string", "hash", "list", "set", "zset", "stream"];
+
+fn populate_encoding_keys(con: &mut redis::Connection) {
+    for i in 0..ENCODING_KEYS_PER_TYPE {
+        let keys: Vec<String> = ENCODING_KEY_TYPES
+            .iter()
+            .map(|key_type| format!("{key_type}:{i}"))
+            .collect();
+        redis::pipe()
+            .del(keys)
+            .ignore()
+            .set(format!("string:{i}"), i)
+            .hset(format!("hash:{i}"), "field", i)
+            .rpush(format!("list:{i}"), i)
+            .sadd(format!("set:{i}"), i)
+            .zadd(format!("zset:{i}"), "member", i)
+            .cmd("XADD")
+            .arg(format!("stream:{i}"))
+            .arg("*")
+            .arg("field")
+            .arg(i)
+            .query::<()>(con)
+            .unwrap();
+    }
+}
+
+fn bench_object_encoding_all_types(b: &mut Bencher) {
+    let client = get_client();
+    let mut con = client.get_connection().unwrap();
+    populate_encoding_keys(&mut con);
+
+    b.iter(|| {
+        for key_type in ENCODING_KEY_TYPES {
+            for i in 0..ENCODING_KEYS_PER_TYPE {
+                let _: Encoding = con.object_encoding(format!("{key_type}:{i}")).unwrap();
+            }
+        }
+    });
+}
+
+fn bench_object_encoding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("object_encoding");
+    group
+        .sample_size(10)
+        .throughput(Throughput::Elements(
+            (ENCODING_KEYS_PER_TYPE * ENCODING_KEY_TYPES.len()) as u64,
+        ))
+        .bench_function("all_types", bench_object_encoding_all_types);
+    group.finish();
+}
+
 fn bench_encode_small(b: &mut Bencher) {
     b.iter(|| {
         let mut cmd = redis::cmd("HSETX");
@@ -278,5 +330,11 @@ fn bench_decode(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(bench, bench_query, bench_encode, bench_decode);
+criterion_group!(
+    bench,
+    bench_query,
+    bench_encode,
+    bench_decode,
+    bench_object_encoding
+);
 criterion_main!(bench);
@@ -1,7 +1,10 @@
 //! Defines types to use with the ACL commands.
 
+use std::collections::HashMap;
+
 use crate::types::{
-    ErrorKind, FromRedisValue, RedisError, RedisResult, RedisWrite, ToRedisArgs, Value,
+    from_redis_value, ErrorKind, FromRedisValue, RedisError, RedisResult, RedisWrite, ToRedisArgs,
+    Value,
 };
 
 macro_rules! not_convertible_error {
@@ -235,6 +238,60 @@ impl FromRedisValue for AclInfo {
     }
 }
 
+/// An entry of the ACL log, as returned by the [`ACL LOG`][1] command.
+///
+/// [1]: https://redis.io/commands/acl-log
+#[derive(Debug, PartialEq)]
+pub struct AclLogEntry {
+    /// The number of security events logged within a 60 seconds period
+    /// that were folded into this entry.
+    pub count: u64,
+    /// The reason the event was logged, for instance `command`, `key`,
+    /// `channel` or `auth`.
+    pub reason: String,
+    /// The resource the user had no permission to access, or `AUTH` for
+    /// failed authentications.
+    pub object: String,
+    /// The user the client was authenticated as.
+    pub username: String,
+    /// The age of the entry, in seconds.
+    pub age_seconds: f64,
+    /// The `CLIENT LIST` description of the offending client.
+    pub client_info: String,
+    /// The sequential id of the entry, starting at 0 when the server starts
+    /// (Redis 7.2+).
+    pub entry_id: Option<u64>,
+    /// The UNIX time in milliseconds at which the entry was first logged
+    /// (Redis 7.2+).
+    pub timestamp: Option<u64>,
+}
+
+impl FromRedisValue for AclLogEntry {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let fields: HashMap<String, Value> = from_redis_value(v)?;
+        let field = |name: &str| {
+            fields.get(name).ok_or_else(|| {
+                not_convertible_error!(v, format!("Missing ACL log entry field {name}"))
+            })
+        };
+        let optional_field = |name: &str| match fields.get(name) {
+            Some(value) => from_redis_value(value).map(Some),
+            None => Ok(None),
+        };
+
+        Ok(Self {
+            count: from_redis_value(field("count")?)?,
+            reason: from_redis_value(field("reason")?)?,
+            object: from_redis_value(field("object")?)?,
+            username: from_redis_value(field("username")?)?,
+            age_seconds: from_redis_value(field("age-seconds")?)?,
+            client_info: from_redis_value(field("client-info")?)?,
+            entry_id: optional_field("entry-id")?,
+            timestamp: optional_field("timestamp-created")?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,4 +366,45 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_acl_log_entry_from_redis_value() {
+        let redis_value = Value::Bulk(vec![
+            Value::Data("count".into()),
+            Value::Int(1),
+            Value::Data("reason".into()),
+            Value::Data("command".into()),
+            Value::Data("context".into()),
+            Value::Data("toplevel".into()),
+            Value::Data("object".into()),
+            Value::Data("get".into()),
+            Value::Data("username".into()),
+            Value::Data("someuser".into()),
+            Value::Data("age-seconds".into()),
+            Value::Data("4.0960000000000001".into()),
+            Value::Data("client-info".into()),
+            Value::Data("id=3 addr=127.0.0.1:57275".into()),
+            Value::Data("entry-id".into()),
+            Value::Int(0),
+            Value::Data("timestamp-created".into()),
+            Value::Int(1675361492408),
+            Value::Data("timestamp-last-updated".into()),
+            Value::Int(1675361492408),
+        ]);
+        let entry = AclLogEntry::from_redis_value(&redis_value).expect("Parse successfully");
+
+        assert_eq!(
+            entry,
+            AclLogEntry {
+                count: 1,
+                reason: "command".to_owned(),
+                object: "get".to_owned(),
+                username: "someuser".to_owned(),
+                age_seconds: 4.096,
+                client_info: "id=3 addr=127.0.0.1:57275".to_owned(),
+                entry_id: Some(0),
+                timestamp: Some(1675361492408),
+            }
+        );
+    }
 }
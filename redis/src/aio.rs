@@ -8,6 +8,8 @@ use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::Path;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::task::{self, Poll};
 
 use combine::{parser::combinator::AnySendSyncPartialState, stream::PointerOffset};
@@ -15,7 +17,7 @@ use combine::{parser::combinator::AnySendSyncPartialState, stream::PointerOffset
 #[cfg(feature = "tokio-comp")]
 use ::tokio::net::lookup_host;
 use ::tokio::{
-    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf},
     sync::{mpsc, oneshot},
 };
 
@@ -32,7 +34,9 @@ use futures_util::{
 use pin_project_lite::pin_project;
 
 use crate::cmd::{cmd, Cmd};
-use crate::connection::{ConnectionAddr, ConnectionInfo, Msg, RedisConnectionInfo};
+use crate::connection::{
+    ConnectionAddr, ConnectionInfo, Msg, ProtocolVersion, RedisConnectionInfo,
+};
 
 #[cfg(any(feature = "tokio-comp", feature = "async-std-comp"))]
 use crate::parser::ValueCodec;
@@ -128,7 +132,12 @@ pub trait AsyncStream: AsyncRead + AsyncWrite {}
 impl<S> AsyncStream for S where S: AsyncRead + AsyncWrite {}
 
 /// Represents a `PubSub` connection.
-pub struct PubSub<C = Pin<Box<dyn AsyncStream + Send + Sync>>>(Connection<C>);
+pub struct PubSub<C = Pin<Box<dyn AsyncStream + Send + Sync>>> {
+    con: Connection<C>,
+    // Messages received while waiting for a subscription to be confirmed, which are yielded
+    // first by the message streams.
+    pending: VecDeque<Msg>,
+}
 
 /// Represents a `Monitor` connection.
 pub struct Monitor<C = Pin<Box<dyn AsyncStream + Send + Sync>>>(Connection<C>);
@@ -138,27 +147,65 @@ where
     C: Unpin + AsyncRead + AsyncWrite + Send,
 {
     fn new(con: Connection<C>) -> Self {
-        Self(con)
+        Self {
+            con,
+            pending: VecDeque::new(),
+        }
     }
 
     /// Subscribes to a new channel.
+    ///
+    /// Returns once the server confirmed the subscription to every given channel, so messages
+    /// published afterwards are guaranteed to be received.
     pub async fn subscribe<T: ToRedisArgs>(&mut self, channel: T) -> RedisResult<()> {
-        cmd("SUBSCRIBE").arg(channel).query_async(&mut self.0).await
+        self.subscribe_confirmed("SUBSCRIBE", channel).await
     }
 
     /// Subscribes to a new channel with a pattern.
+    ///
+    /// Returns once the server confirmed the subscription to every given pattern, so messages
+    /// published afterwards are guaranteed to be received.
     pub async fn psubscribe<T: ToRedisArgs>(&mut self, pchannel: T) -> RedisResult<()> {
-        cmd("PSUBSCRIBE")
-            .arg(pchannel)
-            .query_async(&mut self.0)
-            .await
+        self.subscribe_confirmed("PSUBSCRIBE", pchannel).await
+    }
+
+    async fn subscribe_confirmed<T: ToRedisArgs>(
+        &mut self,
+        command: &'static str,
+        channels: T,
+    ) -> RedisResult<()> {
+        self.con.check_abandoned()?;
+        let channels = channels.to_redis_args();
+        self.con.buf.clear();
+        cmd(command)
+            .arg(&channels)
+            .write_packed_command(&mut self.con.buf);
+        self.con.con.write_all(&self.con.buf).await?;
+
+        // The server confirms each channel separately. Messages of earlier subscriptions may
+        // arrive before the confirmations, those are kept for the message streams. Without any
+        // channel the server replies with an error, so at least one reply is read.
+        let confirmation = command.to_ascii_lowercase();
+        let mut remaining = channels.len().max(1);
+        while remaining > 0 {
+            let value = self.con.read_response().await?;
+            if let Some(msg) = Msg::from_value(&value) {
+                self.pending.push_back(msg);
+                continue;
+            }
+            let (kind, _, _): (String, Value, Value) = from_redis_value(&value)?;
+            if kind == confirmation {
+                remaining -= 1;
+            }
+        }
+        Ok(())
     }
 
     /// Unsubscribes from a channel.
     pub async fn unsubscribe<T: ToRedisArgs>(&mut self, channel: T) -> RedisResult<()> {
         cmd("UNSUBSCRIBE")
             .arg(channel)
-            .query_async(&mut self.0)
+            .query_async(&mut self.con)
             .await
     }
 
@@ -166,7 +213,7 @@ where
     pub async fn punsubscribe<T: ToRedisArgs>(&mut self, pchannel: T) -> RedisResult<()> {
         cmd("PUNSUBSCRIBE")
             .arg(pchannel)
-            .query_async(&mut self.0)
+            .query_async(&mut self.con)
             .await
     }
 
@@ -175,9 +222,11 @@ where
     /// The message itself is still generic and can be converted into an appropriate type through
     /// the helper methods on it.
     pub fn on_message(&mut self) -> impl Stream<Item = Msg> + '_ {
-        ValueCodec::default()
-            .framed(&mut self.0.con)
-            .filter_map(|msg| Box::pin(async move { Msg::from_value(&msg.ok()?.ok()?) }))
+        stream::iter(self.pending.drain(..)).chain(
+            ValueCodec::default()
+                .framed(&mut self.con.con)
+                .filter_map(|msg| Box::pin(async move { Msg::from_value(&msg.ok()?.ok()?) })),
+        )
     }
 
     /// Returns [`Stream`] of [`Msg`]s from this [`PubSub`]s subscriptions consuming it.
@@ -187,16 +236,37 @@ where
     /// This can be useful in cases where the stream needs to be returned or held by something other
     /// than the [`PubSub`].
     pub fn into_on_message(self) -> impl Stream<Item = Msg> {
-        ValueCodec::default()
-            .framed(self.0.con)
-            .filter_map(|msg| Box::pin(async move { Msg::from_value(&msg.ok()?.ok()?) }))
+        stream::iter(self.pending).chain(
+            ValueCodec::default()
+                .framed(self.con.con)
+                .filter_map(|msg| Box::pin(async move { Msg::from_value(&msg.ok()?.ok()?) })),
+        )
+    }
+
+    /// Returns [`Stream`] of [`Msg`]s from this [`PubSub`]s subscriptions consuming it.
+    ///
+    /// Unlike [`PubSub::into_on_message`], connection and parse errors are yielded instead of
+    /// being silently skipped.
+    pub(crate) fn into_on_message_result(self) -> impl Stream<Item = RedisResult<Msg>> {
+        stream::iter(self.pending.into_iter().map(Ok)).chain(
+            ValueCodec::default()
+                .framed(self.con.con)
+                .filter_map(|msg| {
+                    Box::pin(async move {
+                        match msg {
+                            Ok(Ok(value)) => Msg::from_value(&value).map(Ok),
+                            Ok(Err(err)) | Err(err) => Some(Err(err)),
+                        }
+                    })
+                }),
+        )
     }
 
     /// Exits from `PubSub` mode and converts [`PubSub`] into [`Connection`].
     pub async fn into_connection(mut self) -> Connection<C> {
-        self.0.exit_pubsub().await.ok();
+        self.con.exit_pubsub().await.ok();
 
-        self.0
+        self.con
     }
 }
 
@@ -239,12 +309,17 @@ pub struct Connection<C = Pin<Box<dyn AsyncStream + Send + Sync>>> {
     buf: Vec<u8>,
     decoder: combine::stream::Decoder<AnySendSyncPartialState, PointerOffset<[u8]>>,
     db: i64,
+    protocol: ProtocolVersion,
 
     // Flag indicating whether the connection was left in the PubSub state after dropping `PubSub`.
     //
     // This flag is checked when attempting to send a command, and if it's raised, we attempt to
     // exit the pubsub state before executing the new request.
     pubsub: bool,
+
+    // Set when a request was abandoned before its responses were read, after which the
+    // responses can no longer be matched to requests.
+    abandoned: bool,
 }
 
 fn assert_sync<T: Sync>() {}
@@ -261,14 +336,18 @@ impl<C> Connection<C> {
             buf,
             decoder,
             db,
+            protocol,
             pubsub,
+            abandoned,
         } = self;
         Connection {
             con: f(con),
             buf,
             decoder,
             db,
+            protocol,
             pubsub,
+            abandoned,
         }
     }
 }
@@ -285,12 +364,40 @@ where
             buf: Vec::new(),
             decoder: combine::stream::Decoder::new(),
             db: connection_info.db,
+            protocol: ProtocolVersion::RESP2,
             pubsub: false,
+            abandoned: false,
         };
         authenticate(connection_info, &mut rv).await?;
+        rv.protocol = connection_info.protocol();
         Ok(rv)
     }
 
+    /// Returns the protocol version negotiated with the server.
+    pub fn get_protocol(&self) -> ProtocolVersion {
+        self.protocol
+    }
+
+    /// Switches the connection to the database `db` with `SELECT`.
+    ///
+    /// Unlike sending `SELECT` as a plain command, this keeps the database
+    /// reported by [`get_db`](ConnectionLike::get_db) up to date.
+    pub async fn select_db(&mut self, db: i64) -> RedisResult<()> {
+        cmd("SELECT").arg(db).query_async::<_, ()>(self).await?;
+        self.db = db;
+        Ok(())
+    }
+
+    fn check_abandoned(&self) -> RedisResult<()> {
+        if self.abandoned {
+            fail!((
+                ErrorKind::IoError,
+                "Connection closed after an abandoned request"
+            ));
+        }
+        Ok(())
+    }
+
     /// Converts this [`Connection`] into [`PubSub`].
     pub fn into_pubsub(self) -> PubSub<C> {
         PubSub::new(self)
@@ -437,6 +544,10 @@ where
         }
     }
 
+    if connection_info.protocol() == ProtocolVersion::RESP3 {
+        cmd("HELLO").arg(3).query_async::<_, Value>(con).await?;
+    }
+
     if connection_info.db != 0 {
         match cmd("SELECT").arg(connection_info.db).query_async(con).await {
             Ok(Value::Okay) => (),
@@ -526,6 +637,14 @@ pub trait ConnectionLike {
     /// also might be incorrect if the connection like object is not
     /// actually connected.
     fn get_db(&self) -> i64;
+
+    /// Notifies the connection that a request was abandoned before its
+    /// responses were read, for instance because it timed out.
+    ///
+    /// Connections that can't tell the late responses apart from the ones of
+    /// the next requests stop accepting requests, and the
+    /// [`ConnectionManager`] reconnects. Does nothing by default.
+    fn abandon_request(&mut self) {}
 }
 
 impl<C> ConnectionLike for Connection<C>
@@ -534,6 +653,7 @@ where
 {
     fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
         (async move {
+            self.check_abandoned()?;
             if self.pubsub {
                 self.exit_pubsub().await?;
             }
@@ -552,6 +672,7 @@ where
         count: usize,
     ) -> RedisFuture<'a, Vec<Value>> {
         (async move {
+            self.check_abandoned()?;
             if self.pubsub {
                 self.exit_pubsub().await?;
             }
@@ -598,6 +719,10 @@ where
     fn get_db(&self) -> i64 {
         self.db
     }
+
+    fn abandon_request(&mut self) {
+        self.abandoned = true;
+    }
 }
 
 // Senders which the result of a single request are sent through
@@ -878,12 +1003,123 @@ where
     }
 }
 
+/// A snapshot of the metrics of a [`MultiplexedConnection`], as returned by
+/// [`MultiplexedConnection::stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionStats {
+    /// The number of requests currently waiting for a response.
+    pub pending_requests: usize,
+    /// The number of bytes written to the underlying stream.
+    pub bytes_sent: u64,
+    /// The number of bytes read from the underlying stream.
+    pub bytes_received: u64,
+    /// The number of commands sent, counting every command of a pipeline.
+    pub commands_sent: u64,
+}
+
+// Live counters shared by all clones of a `MultiplexedConnection`
+#[derive(Debug, Default)]
+struct StatsCounters {
+    pending_requests: AtomicUsize,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    commands_sent: AtomicU64,
+}
+
+// Marks a request as pending for as long as it is alive, so that requests
+// which are dropped before completing are accounted for as well.
+struct PendingGuard<'a>(&'a StatsCounters);
+
+impl<'a> PendingGuard<'a> {
+    fn new(counters: &'a StatsCounters, commands: usize) -> Self {
+        counters.pending_requests.fetch_add(1, Ordering::Relaxed);
+        counters
+            .commands_sent
+            .fetch_add(commands as u64, Ordering::Relaxed);
+        PendingGuard(counters)
+    }
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.pending_requests.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// Wraps the stream of a `MultiplexedConnection` to count the bytes going
+// through it.
+struct CountingStream<C> {
+    inner: C,
+    counters: Arc<StatsCounters>,
+}
+
+impl<C> AsyncRead for CountingStream<C>
+where
+    C: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        let read = buf.filled().len() - before;
+        self.counters
+            .bytes_received
+            .fetch_add(read as u64, Ordering::Relaxed);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<C> AsyncWrite for CountingStream<C>
+where
+    C: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let written = ready!(Pin::new(&mut self.inner).poll_write(cx, buf))?;
+        self.counters
+            .bytes_sent
+            .fetch_add(written as u64, Ordering::Relaxed);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let written = ready!(Pin::new(&mut self.inner).poll_write_vectored(cx, bufs))?;
+        self.counters
+            .bytes_sent
+            .fetch_add(written as u64, Ordering::Relaxed);
+        Poll::Ready(Ok(written))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
 /// A connection object which can be cloned, allowing requests to be be sent concurrently
 /// on the same underlying connection (tcp/unix socket).
 #[derive(Clone)]
 pub struct MultiplexedConnection {
     pipeline: Pipeline<Vec<u8>, Value, RedisError>,
     db: i64,
+    counters: Arc<StatsCounters>,
 }
 
 impl Debug for MultiplexedConnection {
@@ -914,6 +1150,11 @@ impl MultiplexedConnection {
         #[cfg(all(not(feature = "tokio-comp"), not(feature = "async-std-comp")))]
         compile_error!("tokio-comp or async-std-comp features required for aio feature");
 
+        let counters = Arc::new(StatsCounters::default());
+        let stream = CountingStream {
+            inner: stream,
+            counters: counters.clone(),
+        };
         let codec = ValueCodec::default()
             .framed(stream)
             .and_then(|msg| async move { msg });
@@ -922,6 +1163,7 @@ impl MultiplexedConnection {
         let mut con = MultiplexedConnection {
             pipeline,
             db: connection_info.db,
+            counters,
         };
         let driver = {
             let auth = authenticate(connection_info, &mut con);
@@ -939,11 +1181,27 @@ impl MultiplexedConnection {
         };
         Ok((con, driver))
     }
+
+    /// Returns a snapshot of the metrics of this connection, shared by all of
+    /// its clones.
+    ///
+    /// The counters are updated on the I/O path with relaxed atomics, so this
+    /// is cheap enough to poll periodically, for instance to export gauges.
+    pub fn stats(&self) -> ConnectionStats {
+        let counters = &self.counters;
+        ConnectionStats {
+            pending_requests: counters.pending_requests.load(Ordering::Relaxed),
+            bytes_sent: counters.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: counters.bytes_received.load(Ordering::Relaxed),
+            commands_sent: counters.commands_sent.load(Ordering::Relaxed),
+        }
+    }
 }
 
 impl ConnectionLike for MultiplexedConnection {
     fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
         (async move {
+            let _pending = PendingGuard::new(&self.counters, 1);
             let value = self
                 .pipeline
                 .send(cmd.get_packed_command())
@@ -965,6 +1223,7 @@ impl ConnectionLike for MultiplexedConnection {
         count: usize,
     ) -> RedisFuture<'a, Vec<Value>> {
         (async move {
+            let _pending = PendingGuard::new(&self.counters, offset + count);
             let mut value = self
                 .pipeline
                 .send_recv_multiple(cmd.get_packed_pipeline(), offset + count)
@@ -1032,6 +1291,8 @@ mod connection_manager {
         /// The `ArcSwap` is required to be able to replace the connection
         /// without making the `ConnectionManager` mutable.
         connection: Arc<ArcSwap<SharedRedisFuture<MultiplexedConnection>>>,
+        /// Creates every connection, including the ones made when reconnecting.
+        connection_factory: ConnectionFactory,
 
         runtime: Runtime,
     }
@@ -1039,6 +1300,11 @@ mod connection_manager {
     /// A `RedisResult` that can be cloned because `RedisError` is behind an `Arc`.
     type CloneableRedisResult<T> = Result<T, Arc<RedisError>>;
 
+    /// Type alias for the function used to establish a new connection.
+    type ConnectionFactory = Arc<
+        dyn Fn(&Client) -> BoxFuture<'static, RedisResult<MultiplexedConnection>> + Send + Sync,
+    >;
+
     /// Type alias for a shared boxed future that will resolve to a `CloneableRedisResult`.
     type SharedRedisFuture<T> = Shared<BoxFuture<'static, CloneableRedisResult<T>>>;
 
@@ -1048,10 +1314,53 @@ mod connection_manager {
         /// This requires the `connection-manager` feature, which will also pull in
         /// the Tokio executor.
         pub async fn new(client: Client) -> RedisResult<Self> {
+            Self::with_connection_factory(client, |client| {
+                let client = client.clone();
+                async move { client.get_multiplexed_async_connection().await }
+            })
+            .await
+        }
+
+        /// Connect to the server using `factory` and store the connection inside the
+        /// returned `ConnectionManager`.
+        ///
+        /// The factory is called for the initial connection and again on every
+        /// reconnect, which makes it the place to run any per-connection setup such as
+        /// `CLIENT SETNAME`.
+        ///
+        /// ```rust,no_run
+        /// # async fn do_something() -> redis::RedisResult<()> {
+        /// let client = redis::Client::open("redis://127.0.0.1/")?;
+        /// let manager = redis::aio::ConnectionManager::with_connection_factory(client, |client| {
+        ///     let client = client.clone();
+        ///     async move {
+        ///         let mut con = client.get_multiplexed_async_connection().await?;
+        ///         redis::cmd("CLIENT")
+        ///             .arg("SETNAME")
+        ///             .arg("my-service")
+        ///             .query_async::<_, ()>(&mut con)
+        ///             .await?;
+        ///         Ok(con)
+        ///     }
+        /// })
+        /// .await?;
+        /// # Ok(()) }
+        /// ```
+        pub async fn with_connection_factory<F, Fut>(
+            client: Client,
+            factory: F,
+        ) -> RedisResult<Self>
+        where
+            F: Fn(&Client) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = RedisResult<MultiplexedConnection>> + Send + 'static,
+        {
+            let connection_factory: ConnectionFactory =
+                Arc::new(move |client: &Client| factory(client).boxed());
+
             // Create a MultiplexedConnection and wait for it to be established
 
             let runtime = Runtime::locate();
-            let connection = client.get_multiplexed_async_connection().await?;
+            let connection = connection_factory(&client).await?;
 
             // Wrap the connection in an `ArcSwap` instance for fast atomic access
             Ok(Self {
@@ -1059,6 +1368,7 @@ mod connection_manager {
                 connection: Arc::new(ArcSwap::from_pointee(
                     future::ok(connection).boxed().shared(),
                 )),
+                connection_factory,
                 runtime,
             })
         }
@@ -1071,11 +1381,9 @@ mod connection_manager {
             &self,
             current: arc_swap::Guard<Arc<SharedRedisFuture<MultiplexedConnection>>>,
         ) {
-            let client = self.client.clone();
+            let connection = (self.connection_factory)(&self.client);
             let new_connection: SharedRedisFuture<MultiplexedConnection> =
-                async move { Ok(client.get_multiplexed_async_connection().await?) }
-                    .boxed()
-                    .shared();
+                async move { Ok(connection.await?) }.boxed().shared();
 
             // Update the connection in the connection manager
             let new_connection_arc = Arc::new(new_connection.clone());
@@ -1158,6 +1466,11 @@ mod connection_manager {
         fn get_db(&self) -> i64 {
             self.client.connection_info().redis.db
         }
+
+        fn abandon_request(&mut self) {
+            let current = self.connection.load();
+            self.reconnect(current);
+        }
     }
 }
 
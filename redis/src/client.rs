@@ -8,6 +8,9 @@ use crate::{
     types::{RedisResult, Value},
 };
 
+#[cfg(any(feature = "tokio-comp", feature = "async-std-comp"))]
+use crate::{connection::Msg, types::ToRedisArgs};
+
 /// The client type.
 #[derive(Debug, Clone)]
 pub struct Client {
@@ -88,6 +91,40 @@ impl Client {
         crate::aio::Connection::new(&self.connection_info.redis, con).await
     }
 
+    /// Opens a dedicated async connection, subscribes it to all of the given `channels` and
+    /// returns a [`Stream`](futures_util::Stream) of the messages published to them.
+    ///
+    /// The connection is owned by the returned stream, so dropping the stream closes it and the
+    /// server cleans up the subscriptions.
+    ///
+    /// ```rust,no_run
+    /// # async fn do_something() -> redis::RedisResult<()> {
+    /// use futures::StreamExt;
+    ///
+    /// let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// let mut messages = client.pubsub_listen(&["news", "weather"]).await?;
+    /// while let Some(msg) = messages.next().await {
+    ///     let payload: String = msg?.get_payload()?;
+    ///     println!("{}", payload);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[cfg(any(feature = "tokio-comp", feature = "async-std-comp"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "tokio-comp", feature = "async-std-comp")))
+    )]
+    pub async fn pubsub_listen<C: ToRedisArgs>(
+        &self,
+        channels: &[C],
+    ) -> RedisResult<impl futures_util::Stream<Item = RedisResult<Msg>>> {
+        let mut pubsub = self.get_async_connection().await?.into_pubsub();
+        for channel in channels {
+            pubsub.subscribe(channel).await?;
+        }
+        Ok(pubsub.into_on_message_result())
+    }
+
     /// Returns an async connection from the client.
     #[cfg(feature = "tokio-comp")]
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio-comp")))]
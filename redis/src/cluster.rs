@@ -42,9 +42,14 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::iter::Iterator;
 use std::str::FromStr;
+use std::sync::Arc;
+#[cfg(feature = "tls")]
+use std::sync::RwLock;
 use std::thread;
 use std::time::Duration;
 
+#[cfg(feature = "cluster-diagnostics")]
+use log::debug;
 use rand::{
     seq::{IteratorRandom, SliceRandom},
     thread_rng, Rng,
@@ -52,16 +57,24 @@ use rand::{
 
 use crate::cluster_client::ClusterParams;
 use crate::cluster_pipeline::UNROUTABLE_ERROR;
-use crate::cluster_routing::{Routable, RoutingInfo, Slot, SLOT_SIZE};
+use crate::cluster_routing::{ReadPreferenceFn, Routable, RoutingInfo, Slot, SLOT_SIZE};
 use crate::cmd::{cmd, Cmd};
 use crate::connection::{
     connect, Connection, ConnectionAddr, ConnectionInfo, ConnectionLike, RedisConnectionInfo,
 };
+#[cfg(feature = "tls")]
+use crate::connection::{connect_with_tls_identity, RedisIdentity};
 use crate::parser::parse_redis_value;
-use crate::types::{ErrorKind, HashMap, HashSet, RedisError, RedisResult, Value};
+#[cfg(feature = "cluster-diagnostics")]
+use crate::types::Encoding;
+use crate::types::{ErrorKind, HashMap, HashSet, RedisError, RedisResult, ToRedisArgs, Value};
 
 pub use crate::cluster_client::{ClusterClient, ClusterClientBuilder};
 pub use crate::cluster_pipeline::{cluster_pipe, ClusterPipeline};
+pub use crate::cluster_routing::{key_hash_slot, ReadPreference, RequestRouter};
+
+#[cfg(feature = "cluster-diagnostics")]
+const DEFAULT_DIAGNOSTICS_SAMPLE_RATE: u32 = 100;
 
 /// This is a connection of Redis cluster.
 pub struct ClusterConnection {
@@ -70,11 +83,18 @@ pub struct ClusterConnection {
     slots: RefCell<SlotMap>,
     auto_reconnect: RefCell<bool>,
     read_from_replicas: bool,
+    read_preference_fn: Option<Arc<ReadPreferenceFn>>,
     username: Option<String>,
     password: Option<String>,
     read_timeout: RefCell<Option<Duration>>,
     write_timeout: RefCell<Option<Duration>>,
     tls: Option<TlsMode>,
+    #[cfg(feature = "tls")]
+    tls_identity: Arc<RwLock<Option<RedisIdentity>>>,
+    // One in this many routed keys gets its encoding logged, `None` when disabled.
+    #[cfg(feature = "cluster-diagnostics")]
+    diagnostics_sample_rate: Option<u32>,
+    request_router: Option<Arc<dyn RequestRouter>>,
 }
 
 impl ClusterConnection {
@@ -87,11 +107,26 @@ impl ClusterConnection {
             slots: RefCell::new(SlotMap::new()),
             auto_reconnect: RefCell::new(true),
             read_from_replicas: cluster_params.read_from_replicas,
+            read_preference_fn: cluster_params.read_preference_fn,
             username: cluster_params.username,
             password: cluster_params.password,
             read_timeout: RefCell::new(None),
             write_timeout: RefCell::new(None),
             tls: cluster_params.tls,
+            #[cfg(feature = "tls")]
+            tls_identity: cluster_params.tls_identity,
+            #[cfg(feature = "cluster-diagnostics")]
+            diagnostics_sample_rate: if cluster_params.diagnostics {
+                Some(
+                    cluster_params
+                        .diagnostics_sample_rate
+                        .unwrap_or(DEFAULT_DIAGNOSTICS_SAMPLE_RATE)
+                        .max(1),
+                )
+            } else {
+                None
+            },
+            request_router: cluster_params.request_router,
             initial_nodes: initial_nodes.to_vec(),
         };
         connection.create_initial_connections()?;
@@ -158,6 +193,65 @@ impl ClusterConnection {
         <Self as ConnectionLike>::check_connection(self)
     }
 
+    /// Sends a command built by `cmd_factory` to every primary node of the
+    /// cluster and returns each node's address along with its reply, sorted by
+    /// address. This is meant for commands such as `FLUSHDB`, `DBSIZE` or
+    /// `CONFIG SET` that apply to a single node.
+    ///
+    /// Without a slot map, that is when a request router is used, the command
+    /// is sent to the nodes currently connected to instead. The first error
+    /// returned by a node is returned.
+    pub fn fan_out<F>(&self, cmd_factory: F) -> RedisResult<Vec<(String, Value)>>
+    where
+        F: Fn() -> Cmd,
+    {
+        let mut connections = self.connections.borrow_mut();
+        let mut addrs: Vec<String> = {
+            let slots = self.slots.borrow();
+            if slots.is_empty() {
+                connections.keys().cloned().collect()
+            } else {
+                slots.values().map(|addrs| addrs[0].clone()).collect()
+            }
+        };
+        addrs.sort();
+        addrs.dedup();
+
+        let mut results = Vec::with_capacity(addrs.len());
+        for addr in addrs {
+            let conn = self.get_connection_by_addr(&mut connections, &addr)?;
+            let value = cmd_factory().query(conn)?;
+            results.push((addr, value));
+        }
+        Ok(results)
+    }
+
+    /// Returns the connection info of the primary serving the hash slot of
+    /// `key` according to the current slot map, with the credentials and TLS
+    /// settings of the cluster, for instance to open a direct connection to
+    /// it for diagnostics.
+    ///
+    /// Returns an error of kind `ClusterDown` if no node serves the slot,
+    /// which is always the case when a request router is used as there is no
+    /// slot map then.
+    pub fn get_primary_for_key<K: ToRedisArgs>(&self, key: K) -> RedisResult<ConnectionInfo> {
+        let args = key.to_redis_args();
+        let key = match args.as_slice() {
+            [key] => key,
+            _ => fail!((ErrorKind::ClientError, "The key must be a single argument")),
+        };
+        let slot = key_hash_slot(key);
+        let addr = {
+            let slots = self.slots.borrow();
+            let (_, addrs) = slots
+                .range(&slot..)
+                .next()
+                .ok_or((ErrorKind::ClusterDown, "Missing slot coverage"))?;
+            addrs[0].clone()
+        };
+        self.node_connection_info(&addr)
+    }
+
     pub(crate) fn execute_pipeline(&mut self, pipe: &ClusterPipeline) -> RedisResult<Vec<Value>> {
         self.send_recv_and_retry_cmds(pipe.commands())
     }
@@ -178,7 +272,11 @@ impl ClusterConnection {
             if let Ok(mut conn) = self.connect(&addr) {
                 if conn.check_connection() {
                     connections.insert(addr, conn);
-                    break;
+                    // With a request router, commands are sent to the initial nodes
+                    // so all of them are connected to.
+                    if self.request_router.is_none() {
+                        break;
+                    }
                 }
             }
         }
@@ -191,7 +289,10 @@ impl ClusterConnection {
         }
 
         *self.connections.borrow_mut() = connections;
-        self.refresh_slots()?;
+        // Proxies routing requests themselves don't support `CLUSTER SLOTS`.
+        if self.request_router.is_none() {
+            self.refresh_slots()?;
+        }
         Ok(())
     }
 
@@ -300,15 +401,30 @@ impl ClusterConnection {
         }
     }
 
-    fn connect(&self, node: &str) -> RedisResult<Connection> {
+    // The connection info of `node`, given as `host:port`, with the credentials and TLS
+    // settings of the cluster.
+    fn node_connection_info(&self, node: &str) -> RedisResult<ConnectionInfo> {
         let params = ClusterParams {
             password: self.password.clone(),
             username: self.username.clone(),
             tls: self.tls,
             ..Default::default()
         };
-        let info = get_connection_info(node, params)?;
+        get_connection_info(node, params)
+    }
 
+    fn connect(&self, node: &str) -> RedisResult<Connection> {
+        let info = self.node_connection_info(node)?;
+
+        #[cfg(feature = "tls")]
+        let mut conn =
+            match *self.tls_identity.read().map_err(|_| {
+                RedisError::from((ErrorKind::ClientError, "TLS identity lock poisoned"))
+            })? {
+                Some(ref identity) => connect_with_tls_identity(&info, None, identity)?,
+                None => connect(&info, None)?,
+            };
+        #[cfg(not(feature = "tls"))]
         let mut conn = connect(&info, None)?;
         if self.read_from_replicas {
             // If READONLY is sent to primary nodes, it will have no effect
@@ -351,7 +467,63 @@ impl ClusterConnection {
         }
     }
 
+    // Returns the address of the initial node the request router picks for the packed
+    // command, if there is a router and it picks one.
+    fn addr_from_router(&self, packed_cmd: &[u8]) -> RedisResult<Option<String>> {
+        let idx = match self.request_router {
+            Some(ref router) => router.route(packed_cmd),
+            None => None,
+        };
+        match idx {
+            Some(idx) => match self.initial_nodes.get(idx) {
+                Some(info) => Ok(Some(info.addr.to_string())),
+                None => Err(RedisError::from((
+                    ErrorKind::ClientError,
+                    "Request router returned an unknown node",
+                    format!("node index {idx}"),
+                ))),
+            },
+            None => Ok(None),
+        }
+    }
+
+    // Same as `addr_from_router`, only packing the command if there is a router.
+    fn cmd_addr_from_router(&self, cmd: &Cmd) -> RedisResult<Option<String>> {
+        match self.request_router {
+            Some(_) => self.addr_from_router(&cmd.get_packed_command()),
+            None => Ok(None),
+        }
+    }
+
+    // Returns the index in the slot map of the node the packed read-only command goes to, 0
+    // for the primary and 1 for the replica, as picked by the read preference function.
+    fn replica_idx(&self, packed_cmd: &[u8]) -> usize {
+        match self.read_preference_fn {
+            Some(ref f) if f(packed_cmd) == ReadPreference::Primary => 0,
+            _ => 1,
+        }
+    }
+
+    // Same as `replica_idx`, only packing the command if there is a read preference function.
+    fn cmd_replica_idx(&self, cmd: &Cmd) -> usize {
+        match self.read_preference_fn {
+            Some(_) => self.replica_idx(&cmd.get_packed_command()),
+            None => 1,
+        }
+    }
+
     fn get_addr_for_cmd(&self, cmd: &Cmd) -> RedisResult<String> {
+        if self.request_router.is_some() {
+            return match self.cmd_addr_from_router(cmd)? {
+                Some(addr) => Ok(addr),
+                // There is no slot map to route by, see `create_initial_connections`.
+                None => {
+                    let node = self.initial_nodes.choose(&mut thread_rng()).unwrap();
+                    Ok(node.addr.to_string())
+                }
+            };
+        }
+
         let slots = self.slots.borrow();
 
         let addr_for_slot = |slot: u16, idx: usize| -> RedisResult<String> {
@@ -368,7 +540,9 @@ impl ClusterConnection {
                 Ok(addr_for_slot(rng.gen_range(0..SLOT_SIZE), 0)?)
             }
             Some(RoutingInfo::MasterSlot(slot)) => Ok(addr_for_slot(slot, 0)?),
-            Some(RoutingInfo::ReplicaSlot(slot)) => Ok(addr_for_slot(slot, 1)?),
+            Some(RoutingInfo::ReplicaSlot(slot)) => {
+                Ok(addr_for_slot(slot, self.cmd_replica_idx(cmd))?)
+            }
             _ => fail!(UNROUTABLE_ERROR),
         }
     }
@@ -408,17 +582,28 @@ impl ClusterConnection {
         Ok(T::merge_results(results))
     }
 
+    // Sends the request to `routed_addr` if given, or routes it by its key otherwise, to the
+    // node at the index returned by `replica_idx` in the slot map for read-only commands.
+    // `replica_idx` is only called for read-only commands.
     #[allow(clippy::unnecessary_unwrap)]
-    fn request<R, T, F>(&self, cmd: &R, mut func: F) -> RedisResult<T>
+    fn request<R, T, F, I>(
+        &self,
+        cmd: &R,
+        routed_addr: Option<String>,
+        replica_idx: I,
+        mut func: F,
+    ) -> RedisResult<T>
     where
         R: ?Sized + Routable,
         T: MergeResults + std::fmt::Debug,
         F: FnMut(&mut Connection) -> RedisResult<T>,
+        I: FnOnce() -> usize,
     {
         let route = match RoutingInfo::for_routable(cmd) {
+            _ if routed_addr.is_some() => None,
             Some(RoutingInfo::Random) => None,
             Some(RoutingInfo::MasterSlot(slot)) => Some((slot, 0)),
-            Some(RoutingInfo::ReplicaSlot(slot)) => Some((slot, 1)),
+            Some(RoutingInfo::ReplicaSlot(slot)) => Some((slot, replica_idx())),
             Some(RoutingInfo::AllNodes) | Some(RoutingInfo::AllMasters) => {
                 return self.execute_on_all_nodes(func);
             }
@@ -427,7 +612,7 @@ impl ClusterConnection {
 
         let mut retries = 16;
         let mut excludes = HashSet::new();
-        let mut redirected = None::<String>;
+        let mut redirected = routed_addr.clone();
         let mut is_asking = false;
         loop {
             // Get target address and response.
@@ -448,7 +633,12 @@ impl ClusterConnection {
                 } else {
                     self.get_connection(&mut connections, route.unwrap())?
                 };
-                (addr, func(conn))
+                let rv = func(conn);
+                #[cfg(feature = "cluster-diagnostics")]
+                if rv.is_ok() && route.is_some() {
+                    self.sample_key_encoding(cmd, conn);
+                }
+                (addr, rv)
             };
 
             match rv {
@@ -479,11 +669,14 @@ impl ClusterConnection {
                             let sleep_time = 2u64.pow(16 - retries.max(9)) * 10;
                             thread::sleep(Duration::from_millis(sleep_time));
                             excludes.clear();
+                            // Routed requests go back to the node the router picked.
+                            redirected = routed_addr.clone();
                             continue;
                         }
                     } else if *self.auto_reconnect.borrow() && err.is_io_error() {
                         self.create_initial_connections()?;
                         excludes.clear();
+                        redirected = routed_addr.clone();
                         continue;
                     } else {
                         return Err(err);
@@ -500,6 +693,37 @@ impl ClusterConnection {
         }
     }
 
+    // Logs the encoding of the key `routable` was routed by, for a sample of the calls, if
+    // diagnostics are enabled.
+    #[cfg(feature = "cluster-diagnostics")]
+    fn sample_key_encoding<R>(&self, routable: &R, conn: &mut Connection)
+    where
+        R: ?Sized + Routable,
+    {
+        let sample_rate = match self.diagnostics_sample_rate {
+            Some(sample_rate) => sample_rate,
+            None => return,
+        };
+        if !thread_rng().gen_ratio(1, sample_rate) {
+            return;
+        }
+
+        // Only commands with the key as first argument, see `RoutingInfo::for_routable`.
+        let key = match routable.command().as_deref() {
+            Some(
+                b"EVAL" | b"EVALSHA" | b"OBJECT" | b"XGROUP" | b"XINFO" | b"XREAD" | b"XREADGROUP",
+            )
+            | None => return,
+            Some(_) => match routable.arg_idx(1) {
+                Some(key) => key,
+                None => return,
+            },
+        };
+
+        let encoding = cmd("OBJECT").arg("ENCODING").arg(key).query(conn);
+        debug!("{}", key_encoding_message(key, &encoding));
+    }
+
     fn send_recv_and_retry_cmds(&self, cmds: &[Cmd]) -> RedisResult<Vec<Value>> {
         // Vector to hold the results, pre-populated with `Nil` values. This allows the original
         // cmd ordering to be re-established by inserting the response directly into the result
@@ -515,14 +739,20 @@ impl ClusterConnection {
         }
 
         // Refresh the slots to ensure that we have a clean slate for the retry attempts.
-        self.refresh_slots()?;
+        if self.request_router.is_none() {
+            self.refresh_slots()?;
+        }
 
         // Given that there are commands that need to be retried, it means something in the cluster
         // topology changed. Execute each command seperately to take advantage of the existing
         // retry logic that handles these cases.
         for retry_idx in to_retry {
             let cmd = &cmds[retry_idx];
-            results[retry_idx] = self.request(cmd, move |conn| conn.req_command(cmd))?;
+            let routed_addr = self.cmd_addr_from_router(cmd)?;
+            let replica_idx = || self.cmd_replica_idx(cmd);
+            results[retry_idx] = self.request(cmd, routed_addr, replica_idx, move |conn| {
+                conn.req_command(cmd)
+            })?;
         }
         Ok(results)
     }
@@ -574,12 +804,20 @@ impl ConnectionLike for ClusterConnection {
     }
 
     fn req_command(&mut self, cmd: &Cmd) -> RedisResult<Value> {
-        self.request(cmd, move |conn| conn.req_command(cmd))
+        let routed_addr = self.cmd_addr_from_router(cmd)?;
+        let replica_idx = || self.cmd_replica_idx(cmd);
+        self.request(cmd, routed_addr, replica_idx, move |conn| {
+            conn.req_command(cmd)
+        })
     }
 
     fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
         let value = parse_redis_value(cmd)?;
-        self.request(&value, move |conn| conn.req_packed_command(cmd))
+        let routed_addr = self.addr_from_router(cmd)?;
+        let replica_idx = || self.replica_idx(cmd);
+        self.request(&value, routed_addr, replica_idx, move |conn| {
+            conn.req_packed_command(cmd)
+        })
     }
 
     fn req_packed_commands(
@@ -589,7 +827,9 @@ impl ConnectionLike for ClusterConnection {
         count: usize,
     ) -> RedisResult<Vec<Value>> {
         let value = parse_redis_value(cmd)?;
-        self.request(&value, move |conn| {
+        let routed_addr = self.addr_from_router(cmd)?;
+        let replica_idx = || self.replica_idx(cmd);
+        self.request(&value, routed_addr, replica_idx, move |conn| {
             conn.req_packed_commands(cmd, offset, count)
         })
     }
@@ -674,6 +914,17 @@ pub enum TlsMode {
     Insecure,
 }
 
+// Describes the `OBJECT ENCODING` reply for `key` logged by the diagnostics.
+#[cfg(feature = "cluster-diagnostics")]
+fn key_encoding_message(key: &[u8], encoding: &RedisResult<Option<Encoding>>) -> String {
+    let key = String::from_utf8_lossy(key);
+    match encoding {
+        Ok(Some(encoding)) => format!("key {key:?} has encoding {encoding}"),
+        Ok(None) => format!("key {key:?} does not exist"),
+        Err(err) => format!("failed to get encoding of key {key:?}: {err}"),
+    }
+}
+
 fn get_random_connection<'a>(
     connections: &'a mut HashMap<String, Connection>,
     excludes: Option<&'a HashSet<String>>,
@@ -786,6 +1037,35 @@ fn get_connection_info(node: &str, cluster_params: ClusterParams) -> RedisResult
     })
 }
 
+/// Opens a connection to a single node, without slot routing, using the credentials and TLS
+/// settings of the cluster.
+pub(crate) fn connect_to_node(
+    host: &str,
+    port: u16,
+    cluster_params: &ClusterParams,
+) -> RedisResult<Connection> {
+    let info = ConnectionInfo {
+        addr: get_connection_addr(host.to_string(), port, cluster_params.tls),
+        redis: RedisConnectionInfo {
+            password: cluster_params.password.clone(),
+            username: cluster_params.username.clone(),
+            ..Default::default()
+        },
+    };
+
+    #[cfg(feature = "tls")]
+    match *cluster_params
+        .tls_identity
+        .read()
+        .map_err(|_| RedisError::from((ErrorKind::ClientError, "TLS identity lock poisoned")))?
+    {
+        Some(ref identity) => connect_with_tls_identity(&info, None, identity),
+        None => connect(&info, None),
+    }
+    #[cfg(not(feature = "tls"))]
+    connect(&info, None)
+}
+
 fn get_connection_addr(host: String, port: u16, tls: Option<TlsMode>) -> ConnectionAddr {
     match tls {
         Some(TlsMode::Secure) => ConnectionAddr::TcpTls {
@@ -801,3 +1081,25 @@ fn get_connection_addr(host: String, port: u16, tls: Option<TlsMode>) -> Connect
         _ => ConnectionAddr::Tcp(host, port),
     }
 }
+
+#[cfg(all(test, feature = "cluster-diagnostics"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_encoding_messages() {
+        assert_eq!(
+            key_encoding_message(b"user:1", &Ok(Some(Encoding::Listpack))),
+            r#"key "user:1" has encoding listpack"#
+        );
+        assert_eq!(
+            key_encoding_message(b"missing", &Ok(None)),
+            r#"key "missing" does not exist"#
+        );
+        let err = RedisError::from((ErrorKind::ResponseError, "An error was signalled"));
+        assert_eq!(
+            key_encoding_message(b"user:1", &Err(err)),
+            r#"failed to get encoding of key "user:1": An error was signalled"#
+        );
+    }
+}
@@ -68,7 +68,7 @@ use std::{
 
 use crate::{
     aio::{ConnectionLike, MultiplexedConnection},
-    cluster_routing::RoutingInfo,
+    cluster_routing::{RequestRouter, RoutingInfo},
     parse_redis_url, Cmd, ConnectionAddr, ConnectionInfo, ErrorKind, IntoConnectionInfo,
     RedisError, RedisFuture, RedisResult, Value,
 };
@@ -93,6 +93,7 @@ const DEFAULT_RETRIES: u32 = 16;
 pub struct Client {
     initial_nodes: Vec<ConnectionInfo>,
     retries: Option<u32>,
+    request_router: Option<Arc<dyn RequestRouter>>,
 }
 
 impl Client {
@@ -117,6 +118,7 @@ impl Client {
         Ok(Client {
             initial_nodes: nodes,
             retries: Some(DEFAULT_RETRIES),
+            request_router: None,
         })
     }
 
@@ -127,13 +129,29 @@ impl Client {
         self
     }
 
+    /// Set a router picking the node each request is sent to, for use behind proxies that
+    /// route requests themselves.
+    ///
+    /// With a router, the connection doesn't discover the cluster topology with
+    /// `CLUSTER SLOTS`. Requests the router doesn't pick a node for, and requests retried
+    /// after an error from the picked node, go to a random initial node.
+    pub fn set_request_router(&mut self, router: Arc<dyn RequestRouter>) -> &mut Self {
+        self.request_router = Some(router);
+        self
+    }
+
     /// Open and get a Redis cluster connection.
     ///
     /// # Errors
     ///
     /// If it is failed to open connections and to create slots, an error is returned.
     pub async fn get_connection(&self) -> RedisResult<Connection> {
-        Connection::new(&self.initial_nodes, self.retries).await
+        Connection::new(
+            &self.initial_nodes,
+            self.retries,
+            self.request_router.clone(),
+        )
+        .await
     }
 
     #[doc(hidden)]
@@ -141,7 +159,12 @@ impl Client {
     where
         C: ConnectionLike + Connect + Clone + Send + Sync + Unpin + 'static,
     {
-        Connection::new(&self.initial_nodes, self.retries).await
+        Connection::new(
+            &self.initial_nodes,
+            self.retries,
+            self.request_router.clone(),
+        )
+        .await
     }
 }
 
@@ -156,22 +179,25 @@ where
     async fn new(
         initial_nodes: &[ConnectionInfo],
         retries: Option<u32>,
+        request_router: Option<Arc<dyn RequestRouter>>,
     ) -> RedisResult<Connection<C>> {
-        Pipeline::new(initial_nodes, retries).await.map(|pipeline| {
-            let (tx, mut rx) = mpsc::channel::<Message<_>>(100);
-            let stream = async move {
-                let _ = stream::poll_fn(move |cx| rx.poll_recv(cx))
-                    .map(Ok)
-                    .forward(pipeline)
-                    .await;
-            };
-            #[cfg(feature = "tokio-comp")]
-            tokio::spawn(stream);
-            #[cfg(all(not(feature = "tokio-comp"), feature = "async-std-comp"))]
-            AsyncStd::spawn(stream);
+        Pipeline::new(initial_nodes, retries, request_router)
+            .await
+            .map(|pipeline| {
+                let (tx, mut rx) = mpsc::channel::<Message<_>>(100);
+                let stream = async move {
+                    let _ = stream::poll_fn(move |cx| rx.poll_recv(cx))
+                        .map(Ok)
+                        .forward(pipeline)
+                        .await;
+                };
+                #[cfg(feature = "tokio-comp")]
+                tokio::spawn(stream);
+                #[cfg(all(not(feature = "tokio-comp"), feature = "async-std-comp"))]
+                AsyncStd::spawn(stream);
 
-            Connection(tx)
-        })
+                Connection(tx)
+            })
     }
 }
 
@@ -191,6 +217,10 @@ struct Pipeline<C> {
     pending_requests: Vec<PendingRequest<Response, C>>,
     retries: Option<u32>,
     tls: bool,
+    request_router: Option<Arc<dyn RequestRouter>>,
+    // The keys of the initial nodes in `connections`, in order, to look up the nodes picked
+    // by `request_router`.
+    initial_addrs: Vec<String>,
 }
 
 #[derive(Clone)]
@@ -246,6 +276,25 @@ impl<C> CmdArg<C> {
             }
         }
     }
+
+    // Same as `slot`, for the index of the initial node picked by `router`.
+    fn routed_node(&self, router: &dyn RequestRouter) -> Option<usize> {
+        match self {
+            Self::Cmd { ref cmd, .. } => router.route(&cmd.get_packed_command()),
+            Self::Pipeline { ref pipeline, .. } => {
+                let mut iter = pipeline.cmd_iter();
+                let node = iter
+                    .next()
+                    .map(|cmd| router.route(&cmd.get_packed_command()))?;
+                for cmd in iter {
+                    if node != router.route(&cmd.get_packed_command()) {
+                        return None;
+                    }
+                }
+                node
+            }
+        }
+    }
 }
 
 enum Response {
@@ -282,6 +331,7 @@ impl<C> fmt::Debug for ConnectionState<C> {
 struct RequestInfo<C> {
     cmd: CmdArg<C>,
     slot: Option<u16>,
+    routed_addr: Option<String>,
     excludes: HashSet<String>,
 }
 
@@ -430,7 +480,11 @@ impl<C> Pipeline<C>
 where
     C: ConnectionLike + Connect + Clone + Send + Sync + 'static,
 {
-    async fn new(initial_nodes: &[ConnectionInfo], retries: Option<u32>) -> RedisResult<Self> {
+    async fn new(
+        initial_nodes: &[ConnectionInfo],
+        retries: Option<u32>,
+        request_router: Option<Arc<dyn RequestRouter>>,
+    ) -> RedisResult<Self> {
         let tls = initial_nodes
             .iter()
             .all(|c| matches!(c.addr, ConnectionAddr::TcpTls { .. }));
@@ -444,7 +498,13 @@ where
             state: ConnectionState::PollComplete,
             retries,
             tls,
+            request_router,
+            initial_addrs: initial_nodes.iter().map(initial_node_addr).collect(),
         };
+        // Proxies routing requests themselves don't support `CLUSTER SLOTS`.
+        if connection.request_router.is_some() {
+            return Ok(connection);
+        }
         let (slots, connections) = connection.refresh_slots().await.map_err(|(err, _)| err)?;
         connection.slots = slots;
         connection.connections = connections;
@@ -456,25 +516,7 @@ where
     ) -> RedisResult<ConnectionMap<C>> {
         let connections = stream::iter(initial_nodes.iter().cloned())
             .map(|info| async move {
-                let addr = match info.addr {
-                    ConnectionAddr::Tcp(ref host, port) => match &info.redis.password {
-                        Some(pw) => format!("redis://:{pw}@{host}:{port}"),
-                        None => format!("redis://{host}:{port}"),
-                    },
-                    ConnectionAddr::TcpTls {
-                        ref host,
-                        port,
-                        insecure,
-                    } => match &info.redis.password {
-                        Some(pw) if insecure => {
-                            format!("rediss://:{pw}@{host}:{port}/#insecure")
-                        }
-                        Some(pw) => format!("rediss://:{pw}@{host}:{port}"),
-                        None if insecure => format!("rediss://{host}:{port}/#insecure"),
-                        None => format!("rediss://{host}:{port}"),
-                    },
-                    _ => panic!("No reach."),
-                };
+                let addr = initial_node_addr(&info);
 
                 let result = connect_and_check(info).await;
                 match result {
@@ -510,8 +552,14 @@ where
     {
         let mut connections = mem::take(&mut self.connections);
         let use_tls = self.tls;
+        let has_router = self.request_router.is_some();
 
         async move {
+            // There are no slots to refresh when requests are routed by the request router.
+            if has_router {
+                return Ok((SlotMap::new(), connections));
+            }
+
             let mut result = Ok(SlotMap::new());
             for (addr, conn) in connections.iter_mut() {
                 let mut conn = conn.clone().await;
@@ -633,7 +681,16 @@ where
     ) -> impl Future<Output = (String, RedisResult<Response>)> {
         // TODO remove clone by changing the ConnectionLike trait
         let cmd = info.cmd.clone();
-        let (addr, conn) = if !info.excludes.is_empty() || info.slot.is_none() {
+        let routed_conn = match info.routed_addr {
+            Some(ref addr) if info.excludes.is_empty() => self
+                .connections
+                .get(addr)
+                .map(|conn| (addr.clone(), conn.clone())),
+            _ => None,
+        };
+        let (addr, conn) = if let Some(routed_conn) = routed_conn {
+            routed_conn
+        } else if !info.excludes.is_empty() || info.slot.is_none() {
             get_random_connection(&self.connections, Some(&info.excludes))
         } else {
             self.get_connection(info.slot.unwrap())
@@ -792,10 +849,29 @@ where
 
         let excludes = HashSet::new();
         let slot = cmd.slot();
+        let routed_node = match self.request_router {
+            Some(ref router) => cmd.routed_node(&**router),
+            None => None,
+        };
+        let routed_addr = match routed_node {
+            Some(idx) => match self.initial_addrs.get(idx) {
+                Some(addr) => Some(addr.clone()),
+                None => {
+                    let _ = sender.send(Err(RedisError::from((
+                        ErrorKind::ClientError,
+                        "Request router returned an unknown node",
+                        format!("node index {idx}"),
+                    ))));
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
 
         let info = RequestInfo {
             cmd,
             slot,
+            routed_addr,
             excludes,
         };
 
@@ -953,7 +1029,9 @@ where
 
 impl Clone for Client {
     fn clone(&self) -> Client {
-        Client::open(self.initial_nodes.clone()).unwrap()
+        let mut client = Client::open(self.initial_nodes.clone()).unwrap();
+        client.request_router = self.request_router.clone();
+        client
     }
 }
 
@@ -985,6 +1063,27 @@ impl Connect for MultiplexedConnection {
     }
 }
 
+// The key of the connection to an initial node in the connection map.
+fn initial_node_addr(info: &ConnectionInfo) -> String {
+    match info.addr {
+        ConnectionAddr::Tcp(ref host, port) => match &info.redis.password {
+            Some(pw) => format!("redis://:{pw}@{host}:{port}"),
+            None => format!("redis://{host}:{port}"),
+        },
+        ConnectionAddr::TcpTls {
+            ref host,
+            port,
+            insecure,
+        } => match &info.redis.password {
+            Some(pw) if insecure => format!("rediss://:{pw}@{host}:{port}/#insecure"),
+            Some(pw) => format!("rediss://:{pw}@{host}:{port}"),
+            None if insecure => format!("rediss://{host}:{port}/#insecure"),
+            None => format!("rediss://{host}:{port}"),
+        },
+        _ => panic!("No reach."),
+    }
+}
+
 async fn connect_and_check<T, C>(info: T) -> RedisResult<C>
 where
     T: IntoConnectionInfo + Send,
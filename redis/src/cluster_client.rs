@@ -1,5 +1,12 @@
-use crate::cluster::{ClusterConnection, TlsMode};
-use crate::connection::{ConnectionAddr, ConnectionInfo, IntoConnectionInfo};
+use std::sync::Arc;
+#[cfg(feature = "tls")]
+use std::sync::RwLock;
+
+use crate::cluster::{connect_to_node, ClusterConnection, ReadPreference, RequestRouter, TlsMode};
+use crate::cluster_routing::ReadPreferenceFn;
+#[cfg(feature = "tls")]
+use crate::connection::RedisIdentity;
+use crate::connection::{Connection, ConnectionAddr, ConnectionInfo, IntoConnectionInfo};
 use crate::types::{ErrorKind, RedisError, RedisResult};
 
 /// Redis cluster specific parameters.
@@ -8,10 +15,24 @@ pub(crate) struct ClusterParams {
     pub(crate) password: Option<String>,
     pub(crate) username: Option<String>,
     pub(crate) read_from_replicas: bool,
+    /// Picks whether each read-only command goes to a replica or to the primary when set.
+    pub(crate) read_preference_fn: Option<Arc<ReadPreferenceFn>>,
     /// tls indicates tls behavior of connections.
     /// When Some(TlsMode), connections use tls and verify certification depends on TlsMode.
     /// When None, connections do not use tls.
     pub(crate) tls: Option<TlsMode>,
+    /// When enabled, the `OBJECT ENCODING` of one in `diagnostics_sample_rate` routed keys
+    /// is logged at debug level.
+    #[cfg(feature = "cluster-diagnostics")]
+    pub(crate) diagnostics: bool,
+    #[cfg(feature = "cluster-diagnostics")]
+    pub(crate) diagnostics_sample_rate: Option<u32>,
+    /// Picks the node of each request in place of the hash slot routing when set.
+    pub(crate) request_router: Option<Arc<dyn RequestRouter>>,
+    /// The client certificate presented to TLS nodes, shared with every connection created
+    /// by the client so that a reloaded identity is picked up by new node connections.
+    #[cfg(feature = "tls")]
+    pub(crate) tls_identity: Arc<RwLock<Option<RedisIdentity>>>,
 }
 
 /// Used to configure and build a [`ClusterClient`].
@@ -143,6 +164,59 @@ impl ClusterClientBuilder {
         self
     }
 
+    /// Enables reading from replicas, letting `f` choose for each read-only command whether it
+    /// goes to a replica or to the primary.
+    ///
+    /// `f` is called with the packed command, for instance to send large reads to replicas
+    /// while keeping small, frequent ones on the primaries. Write commands always go to the
+    /// primaries, and [`ReadPreference::Replica`] falls back to the primary if it has no
+    /// replicas.
+    #[allow(clippy::type_complexity)]
+    pub fn read_preference_fn(
+        mut self,
+        f: Arc<dyn Fn(&[u8]) -> ReadPreference + Send + Sync>,
+    ) -> ClusterClientBuilder {
+        self.cluster_params.read_from_replicas = true;
+        self.cluster_params.read_preference_fn = Some(f);
+        self
+    }
+
+    /// Sets a router picking the node each request is sent to, for use behind proxies that
+    /// route requests themselves.
+    ///
+    /// With a router, the client connects to all the initial nodes and doesn't discover the
+    /// cluster topology with `CLUSTER SLOTS`. Requests the router doesn't pick a node for go
+    /// to a random initial node. Without a router (the default), requests are routed by the
+    /// CRC16 hash slot of their key.
+    pub fn with_request_router(mut self, router: Arc<dyn RequestRouter>) -> ClusterClientBuilder {
+        self.cluster_params.request_router = Some(router);
+        self
+    }
+
+    /// Enables logging the encoding of accessed keys for all new connections (default is
+    /// disabled).
+    ///
+    /// If enabled, `OBJECT ENCODING` is sent for a sample of the keys commands are routed by, and
+    /// the result is emitted with `log::debug!`. This is useful to keep an eye on the data
+    /// structures in use while migrating from one to another. Use
+    /// [`diagnostics_sample_rate`](ClusterClientBuilder::diagnostics_sample_rate) to control how
+    /// many keys are sampled.
+    #[cfg(feature = "cluster-diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cluster-diagnostics")))]
+    pub fn diagnostics(mut self, enabled: bool) -> ClusterClientBuilder {
+        self.cluster_params.diagnostics = enabled;
+        self
+    }
+
+    /// Sets how often keys are sampled when [`diagnostics`](ClusterClientBuilder::diagnostics)
+    /// are enabled: one key in `n` is inspected (default is 100).
+    #[cfg(feature = "cluster-diagnostics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cluster-diagnostics")))]
+    pub fn diagnostics_sample_rate(mut self, n: u32) -> ClusterClientBuilder {
+        self.cluster_params.diagnostics_sample_rate = Some(n);
+        self
+    }
+
     /// Use `build()`.
     #[deprecated(since = "0.22.0", note = "Use build()")]
     pub fn open(self) -> RedisResult<ClusterClient> {
@@ -193,6 +267,35 @@ impl ClusterClient {
         ClusterConnection::new(self.cluster_params.clone(), self.initial_nodes.clone())
     }
 
+    /// Opens a direct connection to the node at `host:port`, using the credentials and TLS
+    /// settings of this client.
+    ///
+    /// Commands sent over the returned [`Connection`] are not routed by hash slot, which is
+    /// useful to query a specific node, for instance with `INFO`.
+    pub fn get_connection_to_node(&self, host: &str, port: u16) -> RedisResult<Connection> {
+        connect_to_node(host, port, &self.cluster_params)
+    }
+
+    /// Replaces the client certificate presented to TLS nodes.
+    ///
+    /// The new identity is shared with clones of this client and with existing
+    /// [`ClusterConnection`]s. Connections created from now on, such as the ones
+    /// made by the next `get_connection()` call or when a node is reconnected,
+    /// authenticate with it; already established node connections are left as they are.
+    ///
+    /// This only applies to the synchronous [`ClusterConnection`]: the async
+    /// [`cluster_async::Client`](crate::cluster_async::Client) is configured separately and
+    /// does not present client certificates.
+    #[cfg(feature = "tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+    pub fn reload_tls_identity(&self, identity: RedisIdentity) -> RedisResult<()> {
+        let mut current = self.cluster_params.tls_identity.write().map_err(|_| {
+            RedisError::from((ErrorKind::ClientError, "TLS identity lock poisoned"))
+        })?;
+        *current = Some(identity);
+        Ok(())
+    }
+
     /// Use `new()`.
     #[deprecated(since = "0.22.0", note = "Use new()")]
     pub fn open<T: IntoConnectionInfo>(initial_nodes: Vec<T>) -> RedisResult<ClusterClient> {
@@ -290,6 +393,18 @@ mod tests {
         assert_eq!(client.cluster_params.username, Some("user1".to_string()));
     }
 
+    #[test]
+    #[cfg(feature = "cluster-diagnostics")]
+    fn give_diagnostics_by_method() {
+        let client = ClusterClientBuilder::new(get_connection_data())
+            .diagnostics(true)
+            .diagnostics_sample_rate(10)
+            .build()
+            .unwrap();
+        assert!(client.cluster_params.diagnostics);
+        assert_eq!(client.cluster_params.diagnostics_sample_rate, Some(10));
+    }
+
     #[test]
     fn give_empty_initial_nodes() {
         let client = ClusterClient::new(Vec::<String>::new());
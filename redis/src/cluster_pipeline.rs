@@ -88,6 +88,16 @@ impl ClusterPipeline {
         &self.commands
     }
 
+    /// Clear a pipeline object's internal data structure.
+    ///
+    /// This allows reusing a pipeline object as a clear object while performing a minimal
+    /// amount of memory released/reallocated.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.ignored_commands.clear();
+    }
+
     /// Executes the pipeline and fetches the return values:
     ///
     /// ```rust,no_run
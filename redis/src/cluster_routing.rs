@@ -6,10 +6,60 @@ use crate::types::Value;
 
 pub(crate) const SLOT_SIZE: u16 = 16384;
 
+/// Picks the node a command is sent to, in place of the hash slot routing of
+/// the cluster client.
+///
+/// This is meant for proxies that route commands themselves and don't
+/// support the cluster commands, such as Twemproxy or Envoy. See
+/// [`ClusterClientBuilder::with_request_router`](crate::cluster::ClusterClientBuilder::with_request_router),
+/// or `cluster_async::Client::set_request_router` for async connections.
+pub trait RequestRouter: Send + Sync {
+    /// Returns the index, in the initial nodes of the client, of the node the
+    /// packed command `cmd` should be sent to.
+    ///
+    /// Returning `None` sends the command to a random initial node: there is
+    /// no slot map to route it by, since the client doesn't query the cluster
+    /// topology when it has a router.
+    fn route(&self, cmd: &[u8]) -> Option<usize>;
+}
+
+/// The kind of node a read-only command is sent to, as picked by the function
+/// given to
+/// [`ClusterClientBuilder::read_preference_fn`](crate::cluster::ClusterClientBuilder::read_preference_fn).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadPreference {
+    /// The primary serving the slot of the command
+    Primary,
+    /// A replica of the primary serving the slot of the command, or the
+    /// primary itself if it has no replicas
+    Replica,
+}
+
+/// Picks the kind of node the packed read-only command it is given goes to.
+pub(crate) type ReadPreferenceFn = dyn Fn(&[u8]) -> ReadPreference + Send + Sync;
+
 fn slot(key: &[u8]) -> u16 {
     crc16::State::<crc16::XMODEM>::calculate(key) % SLOT_SIZE
 }
 
+/// Returns the hash slot of a key, as `CLUSTER KEYSLOT` would.
+///
+/// If the key contains a non-empty `{hashtag}`, only the hashtag is hashed,
+/// so that related keys can be stored in the same slot.
+///
+/// ```rust
+/// use redis::cluster::key_hash_slot;
+///
+/// assert_eq!(key_hash_slot(b"foo"), 12182);
+/// assert_eq!(key_hash_slot(b"{user1}:name"), key_hash_slot(b"{user1}:age"));
+/// ```
+pub fn key_hash_slot(key: &[u8]) -> u16 {
+    match get_hashtag(key) {
+        Some(tag) => slot(tag),
+        None => slot(key),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum RoutingInfo {
     AllNodes,
@@ -44,6 +94,11 @@ impl RoutingInfo {
                 }
             }
             b"XGROUP" | b"XINFO" => r.arg_idx(2).map(|key| RoutingInfo::for_key(cmd, key)),
+            // The key follows the subcommand, `OBJECT HELP` has none.
+            b"OBJECT" => match r.arg_idx(2) {
+                Some(key) => Some(RoutingInfo::for_key(cmd, key)),
+                None => Some(RoutingInfo::Random),
+            },
             b"XREAD" | b"XREADGROUP" => {
                 let streams_position = r.position(b"STREAMS")?;
                 r.arg_idx(streams_position + 1)
@@ -57,12 +112,7 @@ impl RoutingInfo {
     }
 
     pub fn for_key(cmd: &[u8], key: &[u8]) -> RoutingInfo {
-        let key = match get_hashtag(key) {
-            Some(tag) => tag,
-            None => key,
-        };
-
-        let slot = slot(key);
+        let slot = key_hash_slot(key);
         if is_readonly_cmd(cmd) {
             RoutingInfo::ReplicaSlot(slot)
         } else {
@@ -178,7 +228,7 @@ fn get_hashtag(key: &[u8]) -> Option<&[u8]> {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_hashtag, slot, RoutingInfo};
+    use super::{get_hashtag, key_hash_slot, slot, RoutingInfo};
     use crate::{cmd, parser::parse_redis_value};
 
     #[test]
@@ -188,6 +238,20 @@ mod tests {
         assert_eq!(get_hashtag(&b"foo{{bar}}zap"[..]), Some(&b"{bar"[..]));
     }
 
+    #[test]
+    fn test_key_hash_slot() {
+        // Reference values from the cluster specification
+        assert_eq!(key_hash_slot(b"123456789"), 0x31c3);
+        assert_eq!(key_hash_slot(b"foo"), 12182);
+        assert_eq!(key_hash_slot(b"{user1000}.following"), slot(b"user1000"));
+        assert_eq!(
+            key_hash_slot(b"{user1000}.following"),
+            key_hash_slot(b"{user1000}.followers")
+        );
+        assert_eq!(key_hash_slot(b"foo{}{bar}"), slot(b"foo{}{bar}"));
+        assert!(key_hash_slot(b"") < 16384);
+    }
+
     #[test]
     fn test_routing_info_mixed_capatalization() {
         let mut upper = cmd("XREAD");
@@ -331,6 +395,15 @@ mod tests {
                 cmd("XINFO").arg("GROUPS").arg("foo"),
                 Some(RoutingInfo::ReplicaSlot(slot(b"foo"))),
             ),
+            (
+                cmd("OBJECT").arg("ENCODING").arg("foo"),
+                Some(RoutingInfo::ReplicaSlot(slot(b"foo"))),
+            ),
+            (
+                cmd("OBJECT").arg("FREQ").arg("{bar}foo"),
+                Some(RoutingInfo::ReplicaSlot(slot(b"bar"))),
+            ),
+            (cmd("OBJECT").arg("HELP"), Some(RoutingInfo::Random)),
             (
                 cmd("XREADGROUP")
                     .arg("GROUP")
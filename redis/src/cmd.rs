@@ -8,6 +8,8 @@ use futures_util::{
 use std::pin::Pin;
 use std::{fmt, io};
 
+#[cfg(feature = "tracing")]
+use crate::commands::is_keyed_cmd;
 use crate::connection::ConnectionLike;
 use crate::pipeline::Pipeline;
 use crate::types::{from_redis_value, FromRedisValue, RedisResult, RedisWrite, ToRedisArgs};
@@ -408,6 +410,8 @@ impl Cmd {
     /// you can retrieve data.
     #[inline]
     pub fn query<T: FromRedisValue>(&self, con: &mut dyn ConnectionLike) -> RedisResult<T> {
+        #[cfg(feature = "tracing")]
+        let _span = self.span().entered();
         match con.req_command(self) {
             Ok(val) => from_redis_value(&val),
             Err(e) => Err(e),
@@ -421,10 +425,39 @@ impl Cmd {
     where
         C: crate::aio::ConnectionLike,
     {
-        let val = con.req_packed_command(self).await?;
+        let request = con.req_packed_command(self);
+        #[cfg(feature = "tracing")]
+        let request = tracing::Instrument::instrument(request, self.span());
+        let val = request.await?;
         from_redis_value(&val)
     }
 
+    /// The span a command is sent in, recording the command name and, for
+    /// the commands taking a key as first argument, the key when it is a
+    /// string. No other argument is recorded, so that passwords given to
+    /// commands like `AUTH` or `CONFIG SET` don't end up in traces.
+    #[cfg(feature = "tracing")]
+    fn span(&self) -> tracing::Span {
+        let mut args = self.args_iter();
+        let name = match args.next() {
+            Some(Arg::Simple(name)) => name.to_ascii_uppercase(),
+            _ => vec![],
+        };
+        let span = tracing::info_span!(
+            "redis.command",
+            cmd = %String::from_utf8_lossy(&name),
+            key = tracing::field::Empty
+        );
+        if is_keyed_cmd(&name) {
+            if let Some(Arg::Simple(key)) = args.next() {
+                if let Ok(key) = std::str::from_utf8(key) {
+                    span.record("key", key);
+                }
+            }
+        }
+        span
+    }
+
     /// Similar to `query()` but returns an iterator over the items of the
     /// bulk result or iterator.  In normal mode this is not in any way more
     /// efficient than just querying into a `Vec<T>` as it's internally
@@ -618,3 +651,87 @@ mod tests {
         assert_eq!(c.arg_idx(4), None);
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "tracing")]
+mod span_tests {
+    use super::Cmd;
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::{span, Event, Metadata, Subscriber};
+
+    // Collects the fields recorded in spans.
+    #[derive(Clone, Default)]
+    struct Fields(Arc<Mutex<HashMap<String, String>>>);
+
+    impl Visit for Fields {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            let mut fields = self.0.lock().unwrap();
+            fields.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            let mut fields = self.0.lock().unwrap();
+            fields.insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for Fields {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+            attrs.record(&mut self.clone());
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, values: &span::Record<'_>) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    fn span_fields(args: &[&str]) -> HashMap<String, String> {
+        let mut cmd = Cmd::new();
+        for arg in args {
+            cmd.arg(*arg);
+        }
+        let fields = Fields::default();
+        tracing::subscriber::with_default(fields.clone(), || drop(cmd.span()));
+        let fields = fields.0.lock().unwrap();
+        fields.clone()
+    }
+
+    #[test]
+    fn test_span_records_key() {
+        let fields = span_fields(&["get", "foo"]);
+        assert_eq!(fields.get("cmd").map(String::as_str), Some("GET"));
+        assert_eq!(fields.get("key").map(String::as_str), Some("foo"));
+    }
+
+    #[test]
+    fn test_span_omits_secrets() {
+        for args in [
+            &["AUTH", "secret"][..],
+            &["AUTH", "user", "secret"],
+            &["HELLO", "3", "AUTH", "user", "secret"],
+            &["CONFIG", "SET", "requirepass", "secret"],
+            &["ACL", "SETUSER", "user", ">secret"],
+        ] {
+            let fields = span_fields(args);
+            assert_eq!(fields.get("cmd").map(String::as_str), Some(args[0]));
+            assert_eq!(fields.get("key"), None);
+            assert!(!fields.values().any(|value| value.contains("secret")));
+        }
+    }
+}
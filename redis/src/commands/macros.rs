@@ -40,7 +40,7 @@ macro_rules! implement_commands {
             $(
                 $(#[$attr])*
                 #[inline]
-                #[allow(clippy::extra_unused_lifetimes, clippy::needless_lifetimes)]
+                #[allow(deprecated, clippy::extra_unused_lifetimes, clippy::needless_lifetimes)]
                 fn $name<$lifetime, $($tyargs: $ty, )* RV: FromRedisValue>(
                     &mut self $(, $argname: $argty)*) -> RedisResult<RV>
                     { Cmd::$name($($argname),*).query(self) }
@@ -62,6 +62,16 @@ macro_rules! implement_commands {
                 c.iter(self)
             }
 
+            /// Incrementally iterate the keys space for keys matching a pattern
+            /// and holding values of the given type (Redis 6.0+).
+            #[inline]
+            fn scan_match_type<P: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, pattern: P, key_type: KeyType) -> RedisResult<Iter<'_, RV>> {
+                let mut c = cmd("SCAN");
+                c.cursor_arg(0).arg("MATCH").arg(pattern).arg("TYPE").arg(key_type);
+                c.iter(self)
+            }
+
             /// Incrementally iterate hash fields and associated values.
             #[inline]
             fn hscan<K: ToRedisArgs, RV: FromRedisValue>(&mut self, key: K) -> RedisResult<Iter<'_, RV>> {
@@ -80,6 +90,16 @@ macro_rules! implement_commands {
                 c.iter(self)
             }
 
+            /// Incrementally iterate hash fields and associated values with
+            /// the `MATCH` and `COUNT` options set through [`ScanOptions`].
+            #[inline]
+            fn hscan_options<K: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, key: K, opts: ScanOptions) -> RedisResult<Iter<'_, RV>> {
+                let mut c = cmd("HSCAN");
+                c.arg(key).cursor_arg(0).arg(opts);
+                c.iter(self)
+            }
+
             /// Incrementally iterate set elements.
             #[inline]
             fn sscan<K: ToRedisArgs, RV: FromRedisValue>(&mut self, key: K) -> RedisResult<Iter<'_, RV>> {
@@ -113,6 +133,186 @@ macro_rules! implement_commands {
                 c.arg(key).cursor_arg(0).arg("MATCH").arg(pattern);
                 c.iter(self)
             }
+
+            /// Get all the members in a set, collected straight into a `HashSet`.
+            #[inline]
+            fn smembers_as_set<K: ToRedisArgs, RV: FromRedisValue + Eq + ::std::hash::Hash>
+                    (&mut self, key: K) -> RedisResult<::std::collections::HashSet<RV>> {
+                Cmd::smembers(key).query(self)
+            }
+
+            /// Increments the float value of a hash field, returning the new value.
+            ///
+            /// Unlike `hincr`, a `delta` that is NaN or infinite is rejected
+            /// with `InvalidClientConfig` before anything is sent.
+            #[inline]
+            fn hincrbyfloat<K: ToRedisArgs, F: ToRedisArgs>
+                    (&mut self, key: K, field: F, delta: f64) -> RedisResult<f64> {
+                ensure_finite(delta)?;
+                cmd("HINCRBYFLOAT").arg(key).arg(field).arg(delta).query(self)
+            }
+
+            /// Increments the float value of a key, returning the new value.
+            ///
+            /// Unlike `incr`, a `delta` that is NaN or infinite is rejected
+            /// with `InvalidClientConfig` before anything is sent.
+            #[inline]
+            fn incrbyfloat<K: ToRedisArgs>(&mut self, key: K, delta: f64) -> RedisResult<f64> {
+                ensure_finite(delta)?;
+                cmd("INCRBYFLOAT").arg(key).arg(delta).query(self)
+            }
+
+            /// Get one random member from a set.
+            ///
+            /// Returns `None` if the key does not exist.
+            #[inline]
+            fn srandmember<K: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, key: K) -> RedisResult<Option<RV>> {
+                Cmd::srandmember(key).query(self)
+            }
+
+            /// Returns the encodings of the given keys, in the same order, fetched
+            /// in a single round trip.
+            #[inline]
+            fn object_encoding_all<K: ToRedisArgs>(&mut self, keys: &[K]) -> RedisResult<Vec<Encoding>> {
+                let mut pipe = Pipeline::new();
+                for key in keys {
+                    pipe.object_encoding(key);
+                }
+                pipe.query(self)
+            }
+
+            /// Returns the encoding of a key or, if the server can't report it
+            /// (for instance for streams before Redis 7.0), its type. Returns
+            /// `None` if the key does not exist.
+            ///
+            /// `TYPE` is only sent when `OBJECT ENCODING` fails with an error
+            /// reply.
+            #[inline]
+            fn object_encoding_or_type<K: ToRedisArgs>(&mut self, key: K) -> RedisResult<Option<EncodingOrType>> {
+                let encoding = Cmd::object_encoding(&key);
+                match encoding.query::<Option<Encoding>>(self) {
+                    Ok(encoding) => return Ok(encoding.map(EncodingOrType::Encoding)),
+                    Err(err) if err.is_io_error() => return Err(err),
+                    Err(_) => {}
+                }
+                let key_type: Option<KeyType> = cmd("TYPE").arg(key).query(self)?;
+                Ok(key_type.map(EncodingOrType::Type))
+            }
+
+            /// Sets multiple fields in a hash, each only if it does not exist,
+            /// and returns whether each field was set, in the same order.
+            ///
+            /// Redis has no `HMSETNX`, so this sends one `HSETNX` per field in a
+            /// `MULTI`/`EXEC` transaction.
+            #[inline]
+            fn hset_nx_multiple<K: ToRedisArgs, F: ToRedisArgs, V: ToRedisArgs>
+                    (&mut self, key: K, items: &[(F, V)]) -> RedisResult<Vec<bool>> {
+                hset_nx_pipeline(key, items).query(self)
+            }
+
+            /// Fetches the value and the type of each key with a single pipeline
+            /// of `MGET` and `TYPE` commands. The value is `None` if the key
+            /// does not exist or does not hold a string, the type is `None` if
+            /// the key does not exist.
+            ///
+            /// On a cluster, the keys must all hash to the same slot.
+            #[inline]
+            fn multi_get_with_types<K: ToRedisArgs>
+                    (&mut self, keys: &[K]) -> RedisResult<Vec<(Option<Value>, Option<KeyType>)>> {
+                let replies = get_with_types_pipeline(keys).query(self)?;
+                values_with_types(replies)
+            }
+
+            /// Determine the type of the value stored at a key, `None` if the key
+            /// does not exist.
+            #[inline]
+            fn type_of<K: ToRedisArgs>(&mut self, key: K) -> RedisResult<Option<KeyType>> {
+                cmd("TYPE").arg(key).query(self)
+            }
+
+            /// Returns the time since the last access of a key, `None` if the
+            /// key does not exist.
+            #[inline]
+            fn object_idletime<K: ToRedisArgs>(&mut self, key: K) -> RedisResult<Option<Duration>> {
+                let secs: Option<u64> = Cmd::object_idletime(key).query(self)?;
+                Ok(secs.map(Duration::from_secs))
+            }
+
+            /// Reads the entries following the given `ids` from each stream in
+            /// `keys`, up to `count` per stream, and returns them by stream
+            /// name. Streams without new entries are left out.
+            ///
+            /// ```text
+            /// XREAD [COUNT count] STREAMS key_1 ... key_N ID_1 ... ID_N
+            /// ```
+            #[cfg(feature = "streams")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+            #[inline]
+            fn xread_multiple<K: ToRedisArgs, I: ToRedisArgs>
+                    (&mut self, count: Option<usize>, keys: &[K], ids: &[I])
+                    -> RedisResult<std::collections::HashMap<String, streams::StreamRangeReply>> {
+                let reply = xread_count_cmd(count, keys, ids).query(self)?;
+                Ok(stream_ranges(reply))
+            }
+
+            /// Set the string value of a key and return its old value atomically
+            /// (Redis 6.2+).
+            ///
+            /// Returns `None` if the key did not exist before.
+            #[inline]
+            fn set_get<K: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, key: K, value: V) -> RedisResult<Option<RV>> {
+                Cmd::set_get(key, value).query(self)
+            }
+
+            /// Like `GETRANGE`, but `start_char` and `end_char` (inclusive) are
+            /// offsets in characters of the UTF-8 string stored at key rather
+            /// than in bytes, so characters are never cut in the middle.
+            ///
+            /// This takes a single round trip: as the byte offset of a character
+            /// can't be known without reading the string, up to 4 bytes per
+            /// character are fetched from the start of the value, which are then
+            /// sliced locally. This transfers more than needed for large
+            /// `start_char`, fetching the whole value with `GET` would be
+            /// another option for short strings.
+            #[inline]
+            fn getrange_utf8<K: ToRedisArgs>(&mut self, key: K, start_char: usize, end_char: usize) -> RedisResult<String> {
+                let bytes: Vec<u8> = cmd("GETRANGE").arg(key).arg(0).arg(utf8_range_end(end_char)).query(self)?;
+                slice_utf8(&bytes, start_char, end_char)
+            }
+
+            /// Overwrites the part of the string stored at key starting at
+            /// `offset` and returns the whole updated value.
+            ///
+            /// `SETRANGE` and `GETRANGE` are sent in a `MULTI`/`EXEC`
+            /// transaction, in a single round trip, so the value returned is
+            /// the one this write produced.
+            #[inline]
+            fn setrange_and_return<K: ToRedisArgs, V: ToRedisArgs>
+                    (&mut self, key: K, offset: u64, value: V) -> RedisResult<Vec<u8>> {
+                let (value,): (Vec<u8>,) = setrange_pipeline(key, offset, value).query(self)?;
+                Ok(value)
+            }
+
+            /// Returns the `(major, minor, patch)` version of the server, which
+            /// can be compared to check whether a command is supported:
+            ///
+            /// ```rust,no_run
+            /// # fn do_something() -> redis::RedisResult<()> {
+            /// # let client = redis::Client::open("redis://127.0.0.1/")?;
+            /// # let mut con = client.get_connection()?;
+            /// use redis::Commands;
+            /// if con.server_version()? >= (7, 2, 0) {
+            ///     let (_local, _replicas): (u32, u32) = con.waitaof(1, 0, 100)?;
+            /// }
+            /// # Ok(()) }
+            /// ```
+            #[inline]
+            fn server_version(&mut self) -> RedisResult<(u16, u16, u16)> {
+                let info: InfoDict = cmd("INFO").arg("SERVER").query(self)?;
+                parse_server_version(&info)
+            }
         }
 
         impl Cmd {
@@ -123,6 +323,27 @@ macro_rules! implement_commands {
                     ::std::mem::replace($body, Cmd::new())
                 }
             )*
+
+            /// Get one random member from a set.
+            pub fn srandmember<K: ToRedisArgs>(key: K) -> Self {
+                let mut c = cmd("SRANDMEMBER");
+                c.arg(key);
+                c
+            }
+
+            /// Returns the time in seconds since the last access of a key.
+            pub fn object_idletime<K: ToRedisArgs>(key: K) -> Self {
+                let mut c = cmd("OBJECT");
+                c.arg("IDLETIME").arg(key);
+                c
+            }
+
+            /// Set the string value of a key and return its old value (Redis 6.2+).
+            pub fn set_get<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V) -> Self {
+                let mut c = cmd("SET");
+                c.arg(key).arg(value).arg("GET");
+                c
+            }
         }
 
         /// Implements common redis commands over asynchronous connections. This
@@ -186,6 +407,16 @@ macro_rules! implement_commands {
                 Box::pin(async move { c.iter_async(self).await })
             }
 
+            /// Incrementally iterate the keys space for keys matching a pattern
+            /// and holding values of the given type (Redis 6.0+).
+            #[inline]
+            fn scan_match_type<P: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, pattern: P, key_type: KeyType) -> crate::types::RedisFuture<'_, crate::cmd::AsyncIter<'_, RV>> {
+                let mut c = cmd("SCAN");
+                c.cursor_arg(0).arg("MATCH").arg(pattern).arg("TYPE").arg(key_type);
+                Box::pin(async move { c.iter_async(self).await })
+            }
+
             /// Incrementally iterate hash fields and associated values.
             #[inline]
             fn hscan<K: ToRedisArgs, RV: FromRedisValue>(&mut self, key: K) -> crate::types::RedisFuture<crate::cmd::AsyncIter<'_, RV>> {
@@ -204,6 +435,16 @@ macro_rules! implement_commands {
                 Box::pin(async move {c.iter_async(self).await })
             }
 
+            /// Incrementally iterate hash fields and associated values with
+            /// the `MATCH` and `COUNT` options set through [`ScanOptions`].
+            #[inline]
+            fn hscan_options<K: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, key: K, opts: ScanOptions) -> crate::types::RedisFuture<crate::cmd::AsyncIter<'_, RV>> {
+                let mut c = cmd("HSCAN");
+                c.arg(key).cursor_arg(0).arg(opts);
+                Box::pin(async move {c.iter_async(self).await })
+            }
+
             /// Incrementally iterate set elements.
             #[inline]
             fn sscan<K: ToRedisArgs, RV: FromRedisValue>(&mut self, key: K) -> crate::types::RedisFuture<crate::cmd::AsyncIter<'_, RV>> {
@@ -237,6 +478,194 @@ macro_rules! implement_commands {
                 c.arg(key).cursor_arg(0).arg("MATCH").arg(pattern);
                 Box::pin(async move {c.iter_async(self).await })
             }
+
+            /// Get all the members in a set, collected straight into a `HashSet`.
+            #[inline]
+            fn smembers_as_set<K: ToRedisArgs, RV: FromRedisValue + Eq + ::std::hash::Hash>
+                    (&mut self, key: K) -> crate::types::RedisFuture<'_, ::std::collections::HashSet<RV>> {
+                let c = Cmd::smembers(key);
+                Box::pin(async move { c.query_async(self).await })
+            }
+
+            /// Increments the float value of a hash field, returning the new value.
+            ///
+            /// Unlike `hincr`, a `delta` that is NaN or infinite is rejected
+            /// with `InvalidClientConfig` before anything is sent.
+            #[inline]
+            fn hincrbyfloat<K: ToRedisArgs, F: ToRedisArgs>
+                    (&mut self, key: K, field: F, delta: f64) -> crate::types::RedisFuture<'_, f64> {
+                let mut c = cmd("HINCRBYFLOAT");
+                c.arg(key).arg(field).arg(delta);
+                Box::pin(async move {
+                    ensure_finite(delta)?;
+                    c.query_async(self).await
+                })
+            }
+
+            /// Increments the float value of a key, returning the new value.
+            ///
+            /// Unlike `incr`, a `delta` that is NaN or infinite is rejected
+            /// with `InvalidClientConfig` before anything is sent.
+            #[inline]
+            fn incrbyfloat<K: ToRedisArgs>
+                    (&mut self, key: K, delta: f64) -> crate::types::RedisFuture<'_, f64> {
+                let mut c = cmd("INCRBYFLOAT");
+                c.arg(key).arg(delta);
+                Box::pin(async move {
+                    ensure_finite(delta)?;
+                    c.query_async(self).await
+                })
+            }
+
+            /// Get one random member from a set.
+            ///
+            /// Returns `None` if the key does not exist.
+            #[inline]
+            fn srandmember<K: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, key: K) -> crate::types::RedisFuture<'_, Option<RV>> {
+                let c = Cmd::srandmember(key);
+                Box::pin(async move { c.query_async(self).await })
+            }
+
+            /// Returns the encodings of the given keys, in the same order, fetched
+            /// in a single round trip.
+            #[inline]
+            fn object_encoding_all<K: ToRedisArgs>
+                    (&mut self, keys: &[K]) -> crate::types::RedisFuture<'_, Vec<Encoding>> {
+                let mut pipe = Pipeline::new();
+                for key in keys {
+                    pipe.object_encoding(key);
+                }
+                Box::pin(async move { pipe.query_async(self).await })
+            }
+
+            /// Returns the encoding of a key or, if the server can't report it, its
+            /// type, see [`Commands::object_encoding_or_type`].
+            #[inline]
+            fn object_encoding_or_type<K: ToRedisArgs>
+                    (&mut self, key: K) -> crate::types::RedisFuture<'_, Option<EncodingOrType>> {
+                let encoding = Cmd::object_encoding(&key);
+                let mut key_type = cmd("TYPE");
+                key_type.arg(key);
+                Box::pin(async move {
+                    match encoding.query_async::<_, Option<Encoding>>(self).await {
+                        Ok(encoding) => return Ok(encoding.map(EncodingOrType::Encoding)),
+                        Err(err) if err.is_io_error() => return Err(err),
+                        Err(_) => {}
+                    }
+                    let key_type: Option<KeyType> = key_type.query_async(self).await?;
+                    Ok(key_type.map(EncodingOrType::Type))
+                })
+            }
+
+            /// Sets multiple fields in a hash, each only if it does not exist, see
+            /// [`Commands::hset_nx_multiple`].
+            #[inline]
+            fn hset_nx_multiple<K: ToRedisArgs, F: ToRedisArgs, V: ToRedisArgs>
+                    (&mut self, key: K, items: &[(F, V)]) -> crate::types::RedisFuture<'_, Vec<bool>> {
+                let pipe = hset_nx_pipeline(key, items);
+                Box::pin(async move { pipe.query_async(self).await })
+            }
+
+            /// Fetches the value and the type of each key, see
+            /// [`Commands::multi_get_with_types`].
+            #[inline]
+            fn multi_get_with_types<K: ToRedisArgs>
+                    (&mut self, keys: &[K]) -> crate::types::RedisFuture<'_, Vec<(Option<Value>, Option<KeyType>)>> {
+                let pipe = get_with_types_pipeline(keys);
+                Box::pin(async move {
+                    let replies = pipe.query_async(self).await?;
+                    values_with_types(replies)
+                })
+            }
+
+            /// Determine the type of the value stored at a key, `None` if the key
+            /// does not exist.
+            #[inline]
+            fn type_of<K: ToRedisArgs>
+                    (&mut self, key: K) -> crate::types::RedisFuture<'_, Option<KeyType>> {
+                let mut c = cmd("TYPE");
+                c.arg(key);
+                Box::pin(async move { c.query_async(self).await })
+            }
+
+            /// Returns the time since the last access of a key, `None` if the
+            /// key does not exist.
+            #[inline]
+            fn object_idletime<K: ToRedisArgs>
+                    (&mut self, key: K) -> crate::types::RedisFuture<'_, Option<Duration>> {
+                let c = Cmd::object_idletime(key);
+                Box::pin(async move {
+                    let secs: Option<u64> = c.query_async(self).await?;
+                    Ok(secs.map(Duration::from_secs))
+                })
+            }
+
+            /// Reads the entries following the given `ids` from each stream in
+            /// `keys`, up to `count` per stream, and returns them by stream
+            /// name, see [`Commands::xread_multiple`].
+            #[cfg(feature = "streams")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+            #[inline]
+            fn xread_multiple<K: ToRedisArgs, I: ToRedisArgs>
+                    (&mut self, count: Option<usize>, keys: &[K], ids: &[I])
+                    -> crate::types::RedisFuture<'_, std::collections::HashMap<String, streams::StreamRangeReply>> {
+                let c = xread_count_cmd(count, keys, ids);
+                Box::pin(async move {
+                    let reply = c.query_async(self).await?;
+                    Ok(stream_ranges(reply))
+                })
+            }
+
+            /// Set the string value of a key and return its old value atomically
+            /// (Redis 6.2+).
+            ///
+            /// Returns `None` if the key did not exist before.
+            #[inline]
+            fn set_get<K: ToRedisArgs, V: ToRedisArgs, RV: FromRedisValue>
+                    (&mut self, key: K, value: V) -> crate::types::RedisFuture<'_, Option<RV>> {
+                let c = Cmd::set_get(key, value);
+                Box::pin(async move { c.query_async(self).await })
+            }
+
+            /// Like `GETRANGE`, but `start_char` and `end_char` (inclusive) are
+            /// offsets in characters of the UTF-8 string stored at key rather
+            /// than in bytes, see [`Commands::getrange_utf8`].
+            #[inline]
+            fn getrange_utf8<K: ToRedisArgs>
+                    (&mut self, key: K, start_char: usize, end_char: usize) -> crate::types::RedisFuture<'_, String> {
+                let mut c = cmd("GETRANGE");
+                c.arg(key).arg(0).arg(utf8_range_end(end_char));
+                Box::pin(async move {
+                    let bytes: Vec<u8> = c.query_async(self).await?;
+                    slice_utf8(&bytes, start_char, end_char)
+                })
+            }
+
+            /// Overwrites the part of the string stored at key starting at
+            /// `offset` and returns the whole updated value, see
+            /// [`Commands::setrange_and_return`].
+            #[inline]
+            fn setrange_and_return<K: ToRedisArgs, V: ToRedisArgs>
+                    (&mut self, key: K, offset: u64, value: V) -> crate::types::RedisFuture<'_, Vec<u8>> {
+                let pipe = setrange_pipeline(key, offset, value);
+                Box::pin(async move {
+                    let (value,): (Vec<u8>,) = pipe.query_async(self).await?;
+                    Ok(value)
+                })
+            }
+
+            /// Returns the `(major, minor, patch)` version of the server, which
+            /// can be compared to check whether a command is supported.
+            #[inline]
+            fn server_version(&mut self) -> crate::types::RedisFuture<'_, (u16, u16, u16)> {
+                let mut c = cmd("INFO");
+                c.arg("SERVER");
+                Box::pin(async move {
+                    let info: InfoDict = c.query_async(self).await?;
+                    parse_server_version(&info)
+                })
+            }
         }
 
         /// Implements common redis commands for pipelines.  Unlike the regular
@@ -253,6 +682,24 @@ macro_rules! implement_commands {
                     self.add_command(::std::mem::replace($body, Cmd::new()))
                 }
             )*
+
+            /// Get one random member from a set.
+            #[inline]
+            pub fn srandmember<K: ToRedisArgs>(&mut self, key: K) -> &mut Self {
+                self.add_command(Cmd::srandmember(key))
+            }
+
+            /// Returns the time in seconds since the last access of a key.
+            #[inline]
+            pub fn object_idletime<K: ToRedisArgs>(&mut self, key: K) -> &mut Self {
+                self.add_command(Cmd::object_idletime(key))
+            }
+
+            /// Set the string value of a key and return its old value (Redis 6.2+).
+            #[inline]
+            pub fn set_get<K: ToRedisArgs, V: ToRedisArgs>(&mut self, key: K, value: V) -> &mut Self {
+                self.add_command(Cmd::set_get(key, value))
+            }
         }
 
         // Implements common redis commands for cluster pipelines.  Unlike the regular
@@ -270,6 +717,24 @@ macro_rules! implement_commands {
                     self.add_command(::std::mem::replace($body, Cmd::new()))
                 }
             )*
+
+            /// Get one random member from a set.
+            #[inline]
+            pub fn srandmember<K: ToRedisArgs>(&mut self, key: K) -> &mut Self {
+                self.add_command(Cmd::srandmember(key))
+            }
+
+            /// Returns the time in seconds since the last access of a key.
+            #[inline]
+            pub fn object_idletime<K: ToRedisArgs>(&mut self, key: K) -> &mut Self {
+                self.add_command(Cmd::object_idletime(key))
+            }
+
+            /// Set the string value of a key and return its old value (Redis 6.2+).
+            #[inline]
+            pub fn set_get<K: ToRedisArgs, V: ToRedisArgs>(&mut self, key: K, value: V) -> &mut Self {
+                self.add_command(Cmd::set_get(key, value))
+            }
         }
     )
 }
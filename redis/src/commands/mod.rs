@@ -3,7 +3,8 @@
 use crate::cmd::{cmd, Cmd, Iter};
 use crate::connection::{Connection, ConnectionLike, Msg};
 use crate::pipeline::Pipeline;
-use crate::types::{FromRedisValue, NumericBehavior, RedisResult, ToRedisArgs, RedisWrite, Expiry};
+use crate::types::{from_redis_value, Encoding, EncodingOrType, ErrorKind, FromRedisValue, InfoDict, KeyType, NumericBehavior, RedisError, RedisResult, ToRedisArgs, RedisWrite, Expiry, Value};
+use std::time::Duration;
 
 #[macro_use]
 mod macros;
@@ -30,6 +31,52 @@ use crate::streams;
 #[cfg(feature = "acl")]
 use crate::acl;
 
+// Whether the first argument of the command `name`, in upper case, is a key.
+#[cfg(feature = "tracing")]
+pub(crate) fn is_keyed_cmd(name: &[u8]) -> bool {
+    matches!(
+        name,
+        // @generic
+        b"COPY" | b"DEL" | b"DUMP" | b"EXISTS" | b"EXPIRE" | b"EXPIREAT" | b"EXPIRETIME" |
+        b"MOVE" | b"PERSIST" | b"PEXPIRE" | b"PEXPIREAT" | b"PEXPIRETIME" | b"PTTL" |
+        b"RENAME" | b"RENAMENX" | b"RESTORE" | b"SORT" | b"SORT_RO" | b"TOUCH" | b"TTL" |
+        b"TYPE" | b"UNLINK" |
+        // @string
+        b"APPEND" | b"DECR" | b"DECRBY" | b"GET" | b"GETDEL" | b"GETEX" | b"GETRANGE" |
+        b"GETSET" | b"INCR" | b"INCRBY" | b"INCRBYFLOAT" | b"LCS" | b"MGET" | b"PSETEX" |
+        b"SET" | b"SETEX" | b"SETNX" | b"SETRANGE" | b"STRLEN" | b"SUBSTR" |
+        // @bitmap
+        b"BITCOUNT" | b"BITFIELD" | b"BITFIELD_RO" | b"BITPOS" | b"GETBIT" | b"SETBIT" |
+        // @hash
+        b"HDEL" | b"HEXISTS" | b"HGET" | b"HGETALL" | b"HINCRBY" | b"HINCRBYFLOAT" |
+        b"HKEYS" | b"HLEN" | b"HMGET" | b"HMSET" | b"HRANDFIELD" | b"HSCAN" | b"HSET" |
+        b"HSETNX" | b"HSTRLEN" | b"HVALS" |
+        // @list
+        b"LINDEX" | b"LINSERT" | b"LLEN" | b"LMOVE" | b"LPOP" | b"LPOS" | b"LPUSH" |
+        b"LPUSHX" | b"LRANGE" | b"LREM" | b"LSET" | b"LTRIM" | b"RPOP" | b"RPOPLPUSH" |
+        b"RPUSH" | b"RPUSHX" |
+        // @set
+        b"SADD" | b"SCARD" | b"SDIFF" | b"SDIFFSTORE" | b"SINTER" | b"SINTERSTORE" |
+        b"SISMEMBER" | b"SMEMBERS" | b"SMISMEMBER" | b"SMOVE" | b"SPOP" | b"SRANDMEMBER" |
+        b"SREM" | b"SSCAN" | b"SUNION" | b"SUNIONSTORE" |
+        // @sortedset
+        b"ZADD" | b"ZCARD" | b"ZCOUNT" | b"ZDIFFSTORE" | b"ZINCRBY" | b"ZINTERSTORE" |
+        b"ZLEXCOUNT" | b"ZMSCORE" | b"ZPOPMAX" | b"ZPOPMIN" | b"ZRANDMEMBER" | b"ZRANGE" |
+        b"ZRANGEBYLEX" | b"ZRANGEBYSCORE" | b"ZRANGESTORE" | b"ZRANK" | b"ZREM" |
+        b"ZREMRANGEBYLEX" | b"ZREMRANGEBYRANK" | b"ZREMRANGEBYSCORE" | b"ZREVRANGE" |
+        b"ZREVRANGEBYLEX" | b"ZREVRANGEBYSCORE" | b"ZREVRANK" | b"ZSCAN" | b"ZSCORE" |
+        b"ZUNIONSTORE" |
+        // @hyperloglog
+        b"PFADD" | b"PFCOUNT" | b"PFMERGE" |
+        // @geo
+        b"GEOADD" | b"GEODIST" | b"GEOHASH" | b"GEOPOS" | b"GEORADIUS" |
+        b"GEORADIUSBYMEMBER" | b"GEOSEARCH" | b"GEOSEARCHSTORE" |
+        // @stream
+        b"XACK" | b"XADD" | b"XAUTOCLAIM" | b"XCLAIM" | b"XDEL" | b"XLEN" | b"XPENDING" |
+        b"XRANGE" | b"XREVRANGE" | b"XSETID" | b"XTRIM"
+    )
+}
+
 #[cfg(feature = "cluster")]
 pub(crate) fn is_readonly_cmd(cmd: &[u8]) -> bool {
     matches!(
@@ -63,6 +110,152 @@ pub(crate) fn is_readonly_cmd(cmd: &[u8]) -> bool {
     )
 }
 
+// Rejects NaN and infinite increments, which the server would refuse anyway.
+fn ensure_finite(delta: f64) -> RedisResult<()> {
+    if delta.is_finite() {
+        Ok(())
+    } else {
+        Err(RedisError::from((
+            ErrorKind::InvalidClientConfig,
+            "Increment must be a finite number",
+        )))
+    }
+}
+
+// Parses `redis_version` out of an `INFO SERVER` reply.
+fn parse_server_version(info: &InfoDict) -> RedisResult<(u16, u16, u16)> {
+    let version: String = info.get("redis_version").ok_or_else(|| {
+        RedisError::from((ErrorKind::TypeError, "Response has no redis_version"))
+    })?;
+    let mut parts = version.split('.').map(str::parse);
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch))) => Ok((major, minor, patch)),
+        _ => Err(RedisError::from((
+            ErrorKind::TypeError,
+            "Invalid server version",
+            version,
+        ))),
+    }
+}
+
+// The number of bytes to fetch so the characters up to `end_char` are
+// complete, as a UTF-8 character is at most 4 bytes long.
+fn utf8_range_end(end_char: usize) -> usize {
+    end_char
+        .saturating_add(1)
+        .saturating_mul(4)
+        .saturating_sub(1)
+        .min(i64::MAX as usize)
+}
+
+// Slices the characters `start_char..=end_char` out of the beginning of a
+// string value, whose last character may have been cut by `GETRANGE`.
+fn slice_utf8(bytes: &[u8], start_char: usize, end_char: usize) -> RedisResult<String> {
+    let s = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(err) if err.error_len().is_none() => {
+            // Only the last character is incomplete
+            std::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap()
+        }
+        Err(_) => {
+            return Err(RedisError::from((
+                ErrorKind::TypeError,
+                "Value is not valid UTF-8",
+            )))
+        }
+    };
+    if end_char < start_char {
+        return Ok(String::new());
+    }
+    Ok(s.chars()
+        .skip(start_char)
+        .take(end_char - start_char + 1)
+        .collect())
+}
+
+// The `XREAD` command reading up to `count` entries from each stream.
+#[cfg(feature = "streams")]
+fn xread_count_cmd<K: ToRedisArgs, I: ToRedisArgs>(count: Option<usize>, keys: &[K], ids: &[I]) -> Cmd {
+    let mut opts = streams::StreamReadOptions::default();
+    if let Some(count) = count {
+        opts = opts.count(count);
+    }
+    Cmd::xread_options(keys, ids, &opts)
+}
+
+// Maps the name of each stream of an `XREAD` reply to its entries.
+#[cfg(feature = "streams")]
+fn stream_ranges(
+    reply: Option<streams::StreamReadReply>,
+) -> std::collections::HashMap<String, streams::StreamRangeReply> {
+    reply
+        .map(|reply| reply.keys)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|key| (key.key, streams::StreamRangeReply { ids: key.ids }))
+        .collect()
+}
+
+// The transaction sending one `HSETNX` per field, whose reply is whether each
+// field was set.
+fn hset_nx_pipeline<K: ToRedisArgs, F: ToRedisArgs, V: ToRedisArgs>(
+    key: K,
+    items: &[(F, V)],
+) -> Pipeline {
+    let mut pipe = Pipeline::new();
+    pipe.atomic();
+    for (field, value) in items {
+        pipe.hset_nx(&key, field, value);
+    }
+    pipe
+}
+
+// The transaction overwriting part of a string value with `SETRANGE`, whose
+// reply is the whole updated value.
+fn setrange_pipeline<K: ToRedisArgs, V: ToRedisArgs>(key: K, offset: u64, value: V) -> Pipeline {
+    let mut pipe = Pipeline::new();
+    pipe.atomic()
+        .cmd("SETRANGE")
+        .arg(&key)
+        .arg(offset)
+        .arg(value)
+        .ignore()
+        .cmd("GETRANGE")
+        .arg(&key)
+        .arg(0)
+        .arg(-1);
+    pipe
+}
+
+// The pipeline fetching the values of `keys` with `MGET`, followed by the
+// type of each key.
+fn get_with_types_pipeline<K: ToRedisArgs>(keys: &[K]) -> Pipeline {
+    let mut pipe = Pipeline::new();
+    if !keys.is_empty() {
+        pipe.cmd("MGET");
+        for key in keys {
+            pipe.arg(key);
+        }
+        for key in keys {
+            pipe.cmd("TYPE").arg(key);
+        }
+    }
+    pipe
+}
+
+// Pairs each value of the `MGET` reply with the type of its key.
+fn values_with_types(replies: Vec<Value>) -> RedisResult<Vec<(Option<Value>, Option<KeyType>)>> {
+    let mut replies = replies.into_iter();
+    let values: Vec<Option<Value>> = match replies.next() {
+        Some(reply) => from_redis_value(&reply)?,
+        None => return Ok(Vec::new()),
+    };
+    let types = replies
+        .map(|reply| from_redis_value(&reply))
+        .collect::<RedisResult<Vec<Option<KeyType>>>>()?;
+    Ok(values.into_iter().zip(types).collect())
+}
+
 implement_commands! {
     'a
     // most common operations
@@ -102,6 +295,11 @@ implement_commands! {
         cmd("PSETEX").arg(key).arg(milliseconds).arg(value)
     }
 
+    /// Set the value of a key, keeping the time to live it already has (Redis 6.0+).
+    fn set_keepttl<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V) {
+        cmd("SET").arg(key).arg(value).arg("KEEPTTL")
+    }
+
     /// Set the value of a key, only if the key does not exist
     fn set_nx<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V) {
         cmd("SETNX").arg(key).arg(value)
@@ -113,6 +311,12 @@ implement_commands! {
     }
 
     /// Set the string value of a key and return its old value.
+    ///
+    /// `GETSET` is deprecated since Redis 6.2, use
+    /// [`set_get`](Commands::set_get) on newer servers. This keeps sending
+    /// `GETSET`, which older servers need: the negotiated protocol doesn't
+    /// tell whether the server supports `SET ... GET`.
+    #[deprecated(since = "0.23.0", note = "Use set_get")]
     fn getset<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V) {
         cmd("GETSET").arg(key).arg(value)
     }
@@ -201,10 +405,29 @@ implement_commands! {
     }
 
     /// Unlink one or more keys.
+    ///
+    /// Unlike `del` the memory is reclaimed in a background thread, so this
+    /// does not block on large values.
     fn unlink<K: ToRedisArgs>(key: K) {
         cmd("UNLINK").arg(key)
     }
 
+    /// Unlink multiple keys, returning how many of them existed.
+    fn unlink_multiple<K: ToRedisArgs>(keys: &'a [K]) {
+        cmd("UNLINK").arg(keys)
+    }
+
+    /// Atomically transfer a key from this instance to the instance at
+    /// `host:port`, selecting `db` there and waiting at most `timeout`
+    /// milliseconds.
+    ///
+    /// To transfer several keys at once, pass an empty string as `key` and
+    /// set the keys with [`MigrateOptions::keys`]. Returns `"OK"`, or `"NOKEY"`
+    /// if none of the keys exist.
+    fn migrate<K: ToRedisArgs>(host: &'a str, port: u16, key: K, db: i64, timeout: usize, options: MigrateOptions) {
+        cmd("MIGRATE").arg(host).arg(port).arg(key).arg(db).arg(timeout).arg(options)
+    }
+
     // common string operations
 
     /// Append a value to a key.
@@ -247,6 +470,34 @@ implement_commands! {
         cmd("BITCOUNT").arg(key).arg(start).arg(end)
     }
 
+    /// Count set bits in a string in a range, given in bytes or bits
+    /// depending on `mode`. Negative offsets count from the end of the
+    /// string. Redis 7.0+.
+    fn bitcount_range_mode<K: ToRedisArgs>(key: K, start: i64, end: i64, mode: RangeMode) {
+        cmd("BITCOUNT").arg(key).arg(start).arg(end).arg(mode)
+    }
+
+    /// Returns the position of the first bit set to 1 or 0 in a string, or
+    /// -1 if there is none. The search can be limited to a range, given in
+    /// bytes unless `mode` says otherwise. `end` is only sent along with
+    /// `start`, and `mode` along with `end`; `mode` needs Redis 7.0+.
+    ///
+    /// ```text
+    /// BITPOS key bit [start [end [BYTE | BIT]]]
+    /// ```
+    fn bitpos<K: ToRedisArgs>(
+        key: K,
+        bit: bool,
+        start: Option<i64>,
+        end: Option<i64>,
+        mode: Option<RangeMode>
+    ) {
+        cmd("BITPOS")
+            .arg(key)
+            .arg(bit)
+            .arg(start.map(|start| (start, end.map(|end| (end, mode)))))
+    }
+
     /// Perform a bitwise AND between multiple keys (containing string values)
     /// and store the result in the destination key.
     fn bit_and<K: ToRedisArgs>(dstkey: K, srckeys: K) {
@@ -332,6 +583,13 @@ implement_commands! {
         cmd("HGETALL").arg(key)
     }
 
+    /// Gets all the fields and values in a hash, for return types that
+    /// implement `FromRedisValue` by reading the field/value pairs. This
+    /// sends the same command as `hgetall`.
+    fn hgetall_as_struct<K: ToRedisArgs>(key: K) {
+        cmd("HGETALL").arg(key)
+    }
+
     /// Gets the length of a hash.
     fn hlen<K: ToRedisArgs>(key: K) {
         cmd("HLEN").arg(key)
@@ -347,7 +605,7 @@ implement_commands! {
 
     /// Pops `count` elements from the first non-empty list key from the list of
     /// provided key names; or blocks until one is available.
-    fn blmpop<K: ToRedisArgs>(timeout: usize, numkeys: usize, key: K, dir: Direction, count: usize){
+    fn blmpop<K: ToRedisArgs>(timeout: usize, numkeys: usize, key: K, dir: PopDirection, count: usize){
         cmd("BLMPOP").arg(timeout).arg(numkeys).arg(key).arg(dir).arg("COUNT").arg(count)
     }
 
@@ -396,7 +654,7 @@ implement_commands! {
 
     /// Pops `count` elements from the first non-empty list key from the list of
     /// provided key names.
-    fn lmpop<K: ToRedisArgs>( numkeys: usize, key: K, dir: Direction, count: usize) {
+    fn lmpop<K: ToRedisArgs>( numkeys: usize, key: K, dir: PopDirection, count: usize) {
         cmd("LMPOP").arg(numkeys).arg(key).arg(dir).arg("COUNT").arg(count)
     }
 
@@ -486,7 +744,9 @@ implement_commands! {
     }
 
     /// Subtract multiple sets and store the resulting set in a key.
-    fn sdiffstore<K: ToRedisArgs>(dstkey: K, keys: K) {
+    ///
+    /// Replies the number of members in the resulting set.
+    fn sdiffstore<D: ToRedisArgs, K: ToRedisArgs>(dstkey: D, keys: K) {
         cmd("SDIFFSTORE").arg(dstkey).arg(keys)
     }
 
@@ -496,7 +756,9 @@ implement_commands! {
     }
 
     /// Intersect multiple sets and store the resulting set in a key.
-    fn sinterstore<K: ToRedisArgs>(dstkey: K, keys: K) {
+    ///
+    /// Replies the number of members in the resulting set.
+    fn sinterstore<D: ToRedisArgs, K: ToRedisArgs>(dstkey: D, keys: K) {
         cmd("SINTERSTORE").arg(dstkey).arg(keys)
     }
 
@@ -520,11 +782,6 @@ implement_commands! {
         cmd("SPOP").arg(key)
     }
 
-    /// Get one random member from a set.
-    fn srandmember<K: ToRedisArgs>(key: K) {
-        cmd("SRANDMEMBER").arg(key)
-    }
-
     /// Get multiple random members from a set.
     fn srandmember_multiple<K: ToRedisArgs>(key: K, count: usize) {
         cmd("SRANDMEMBER").arg(key).arg(count)
@@ -541,7 +798,9 @@ implement_commands! {
     }
 
     /// Add multiple sets and store the resulting set in a key.
-    fn sunionstore<K: ToRedisArgs>(dstkey: K, keys: K) {
+    ///
+    /// Replies the number of members in the resulting set.
+    fn sunionstore<D: ToRedisArgs, K: ToRedisArgs>(dstkey: D, keys: K) {
         cmd("SUNIONSTORE").arg(dstkey).arg(keys)
     }
 
@@ -552,6 +811,16 @@ implement_commands! {
         cmd("ZADD").arg(key).arg(score).arg(member)
     }
 
+    /// Add one member to a sorted set with the `NX`, `XX`, `GT`, `LT`, `CH`
+    /// and `INCR` flags set through [`ZaddOptions`].
+    ///
+    /// Replies with the number of added (or, with `CH`, changed) members, or
+    /// with `INCR` the new score of the member, `nil` when a flag prevented
+    /// the update. Read the reply as a [`Value`](crate::Value) to handle both.
+    fn zadd_options<K: ToRedisArgs, S: ToRedisArgs, M: ToRedisArgs>(key: K, member: M, score: S, options: ZaddOptions) {
+        cmd("ZADD").arg(key).arg(options).arg(score).arg(member)
+    }
+
     /// Add multiple members to a sorted set, or update its score if it already exists.
     fn zadd_multiple<K: ToRedisArgs, S: ToRedisArgs, M: ToRedisArgs>(key: K, items: &'a [(S, M)]) {
         cmd("ZADD").arg(key).arg(items)
@@ -642,6 +911,25 @@ implement_commands! {
         cmd("ZMPOP").arg(keys.len()).arg(keys).arg("MIN").arg("COUNT").arg(count)
     }
 
+    /// Removes and returns up to `count` members from the `direction` end of
+    /// the first non-empty sorted set in `keys`, or blocks for up to `timeout`
+    /// seconds (0 blocks forever) until one is available (Redis 7.0+).
+    ///
+    /// The reply reads as `Option<ZMPopReply>`: the key popped from and the
+    /// members with their scores, or `None` on timeout.
+    ///
+    /// As this blocks the connection it is sent on, it should be used on a
+    /// dedicated [`Connection`](crate::Connection): on a multiplexed
+    /// connection or a connection manager, every other request sharing the
+    /// connection waits until it returns.
+    ///
+    /// ```text
+    /// BZMPOP <timeout> <numkeys> <key> [<key> ...] <MIN | MAX> COUNT <count>
+    /// ```
+    fn bzmpop<K: ToRedisArgs>(timeout: f64, keys: &'a [K], direction: SortOrder, count: usize) {
+        cmd("BZMPOP").arg(timeout).arg(keys.len()).arg(keys).arg(direction).arg("COUNT").arg(count)
+    }
+
     /// Return up to count random members in a sorted set (or 1 if `count == None`)
     fn zrandmember<K: ToRedisArgs>(key: K, count: Option<isize>) {
         cmd("ZRANDMEMBER").arg(key).arg(count)
@@ -662,6 +950,17 @@ implement_commands! {
         cmd("ZRANGE").arg(key).arg(start).arg(stop).arg("WITHSCORES")
     }
 
+    /// Return a range of members in a sorted set with the unified `ZRANGE`
+    /// (Redis 6.2+), which selects by index, score or lexicographical range
+    /// in either order depending on the [`ZRangeOptions`].
+    ///
+    /// ```text
+    /// ZRANGE <key> <start> <stop> [BYSCORE | BYLEX] [REV] [LIMIT <offset> <count>] [WITHSCORES]
+    /// ```
+    fn zrange_options<K: ToRedisArgs, S: ToRedisArgs, E: ToRedisArgs>(key: K, start: S, stop: E, options: ZRangeOptions) {
+        cmd("ZRANGE").arg(key).arg(start).arg(stop).arg(options)
+    }
+
     /// Return a range of members in a sorted set, by lexicographical range.
     fn zrangebylex<K: ToRedisArgs, M: ToRedisArgs, MM: ToRedisArgs>(key: K, min: M, max: MM) {
         cmd("ZRANGEBYLEX").arg(key).arg(min).arg(max)
@@ -829,18 +1128,20 @@ implement_commands! {
     // hyperloglog commands
 
     /// Adds the specified elements to the specified HyperLogLog.
+    ///
+    /// Replies `true` (or `1`) if the estimated cardinality was modified.
     fn pfadd<K: ToRedisArgs, E: ToRedisArgs>(key: K, element: E) {
         cmd("PFADD").arg(key).arg(element)
     }
 
     /// Return the approximated cardinality of the set(s) observed by the
-    /// HyperLogLog at key(s).
+    /// HyperLogLog at key(s), as an integer such as `u64`.
     fn pfcount<K: ToRedisArgs>(key: K) {
         cmd("PFCOUNT").arg(key)
     }
 
     /// Merge N different HyperLogLogs into a single one.
-    fn pfmerge<K: ToRedisArgs>(dstkey: K, srckeys: K) {
+    fn pfmerge<D: ToRedisArgs, S: ToRedisArgs>(dstkey: D, srckeys: S) {
         cmd("PFMERGE").arg(dstkey).arg(srckeys)
     }
 
@@ -851,16 +1152,16 @@ implement_commands! {
 
     // Object commands
 
-    /// Returns the encoding of a key.
+    /// Returns the encoding of a key, read it as an [`Encoding`](crate::Encoding)
+    /// or as the `String` the server reports. Reading it as an `Option` gives
+    /// `None` if the key does not exist.
+    ///
+    /// Cluster connections route the command to the node owning the key and
+    /// follow a `MOVED` redirection if their slot map is out of date.
     fn object_encoding<K: ToRedisArgs>(key: K) {
         cmd("OBJECT").arg("ENCODING").arg(key)
     }
 
-    /// Returns the time in seconds since the last access of a key.
-    fn object_idletime<K: ToRedisArgs>(key: K) {
-        cmd("OBJECT").arg("IDLETIME").arg(key)
-    }
-
     /// Returns the logarithmic access frequency counter of a key.
     fn object_freq<K: ToRedisArgs>(key: K) {
         cmd("OBJECT").arg("FREQ").arg(key)
@@ -871,147 +1172,601 @@ implement_commands! {
         cmd("OBJECT").arg("REFCOUNT").arg(key)
     }
 
-    // ACL commands
+    /// Returns the help text of the `OBJECT` subcommands supported by the
+    /// server, one line per element. Read it as `Vec<String>`.
+    fn object_help<>() {
+        cmd("OBJECT").arg("HELP")
+    }
 
-    /// When Redis is configured to use an ACL file (with the aclfile
-    /// configuration option), this command will reload the ACLs from the file,
-    /// replacing all the current ACL rules with the ones defined in the file.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_load<>() {
-        cmd("ACL").arg("LOAD")
+    // Client commands
+
+    /// Suspends all the Redis clients for the specified amount of time (in milliseconds).
+    ///
+    /// The `mode` argument was added in Redis 6.2, older servers only
+    /// understand the form without it and always pause all clients.
+    ///
+    /// ```text
+    /// CLIENT PAUSE <timeout> [WRITE|ALL]
+    /// ```
+    fn client_pause<>(timeout_ms: u64, mode: Option<ClientPauseMode>) {
+        cmd("CLIENT").arg("PAUSE").arg(timeout_ms).arg(mode)
     }
 
-    /// When Redis is configured to use an ACL file (with the aclfile
-    /// configuration option), this command will save the currently defined
-    /// ACLs from the server memory to the ACL file.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_save<>() {
-        cmd("ACL").arg("SAVE")
+    /// Resumes processing of clients that were paused by `CLIENT PAUSE` (Redis 6.2+).
+    fn client_unpause<>() {
+        cmd("CLIENT").arg("UNPAUSE")
     }
 
-    /// Shows the currently active ACL rules in the Redis server.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_list<>() {
-        cmd("ACL").arg("LIST")
+    /// Returns the connected clients of the given type, read them as
+    /// `Vec<ClientInfo>`, see [`ClientInfo`](crate::ClientInfo).
+    ///
+    /// ```text
+    /// CLIENT LIST TYPE <NORMAL|MASTER|REPLICA|PUBSUB>
+    /// ```
+    fn client_list_filtered<>(client_type: ClientType) {
+        cmd("CLIENT").arg("LIST").arg("TYPE").arg(client_type)
     }
 
-    /// Shows a list of all the usernames of the currently configured users in
-    /// the Redis ACL system.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_users<>() {
-        cmd("ACL").arg("USERS")
+    /// Returns the connected clients with the given ids, read them as
+    /// `Vec<ClientInfo>`, see [`ClientInfo`](crate::ClientInfo) (Redis 6.2+).
+    ///
+    /// ```text
+    /// CLIENT LIST ID <id> [<id> ...]
+    /// ```
+    fn client_list_id<>(ids: &'a [u64]) {
+        cmd("CLIENT").arg("LIST").arg("ID").arg(ids)
     }
 
-    /// Returns all the rules defined for an existing ACL user.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_getuser<K: ToRedisArgs>(username: K) {
-        cmd("ACL").arg("GETUSER").arg(username)
+    /// Closes the connection of the client with the given id. Read the reply
+    /// as a `bool` to know whether a client was killed.
+    ///
+    /// ```text
+    /// CLIENT KILL ID <id>
+    /// ```
+    fn client_kill_by_id<>(id: u64) {
+        cmd("CLIENT").arg("KILL").arg("ID").arg(id)
     }
 
-    /// Creates an ACL user without any privilege.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_setuser<K: ToRedisArgs>(username: K) {
-        cmd("ACL").arg("SETUSER").arg(username)
+    /// Closes the connections of the clients connected from `addr`, given as
+    /// `ip:port`, and returns the number of clients killed.
+    ///
+    /// ```text
+    /// CLIENT KILL ADDR <ip:port>
+    /// ```
+    fn client_kill_by_addr<>(addr: &'a str) {
+        cmd("CLIENT").arg("KILL").arg("ADDR").arg(addr)
     }
 
-    /// Creates an ACL user with the specified rules or modify the rules of
-    /// an existing user.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_setuser_rules<K: ToRedisArgs>(username: K, rules: &'a [acl::Rule]) {
-        cmd("ACL").arg("SETUSER").arg(username).arg(rules)
+    /// Sets whether the current connection is excluded from client eviction,
+    /// which closes connections once their memory use exceeds
+    /// `maxmemory-clients` (Redis 7.0+).
+    ///
+    /// ```text
+    /// CLIENT NO-EVICT <ON|OFF>
+    /// ```
+    fn client_no_evict<>(on: bool) {
+        cmd("CLIENT").arg("NO-EVICT").arg(if on { "ON" } else { "OFF" })
     }
 
-    /// Delete all the specified ACL users and terminate all the connections
-    /// that are authenticated with such users.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_deluser<K: ToRedisArgs>(usernames: &'a [K]) {
-        cmd("ACL").arg("DELUSER").arg(usernames)
+    // Server commands
+
+    /// Returns information and statistics about the server, for the given
+    /// section or the default sections. Read the reply as a
+    /// [`ServerInfo`](crate::ServerInfo) for typed access to the fields.
+    ///
+    /// ```text
+    /// INFO [<section>]
+    /// ```
+    fn info<S: ToRedisArgs>(section: Option<S>) {
+        cmd("INFO").arg(section)
     }
 
-    /// Shows the available ACL categories.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_cat<>() {
-        cmd("ACL").arg("CAT")
+    /// Returns the number of keys in the selected database.
+    fn dbsize<>() {
+        &mut cmd("DBSIZE")
     }
 
-    /// Shows all the Redis commands in the specified category.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_cat_categoryname<K: ToRedisArgs>(categoryname: K) {
-        cmd("ACL").arg("CAT").arg(categoryname)
+    /// Returns the total number of commands supported by the server.
+    fn command_count<>() {
+        cmd("COMMAND").arg("COUNT")
     }
 
-    /// Generates a 256-bits password starting from /dev/urandom if available.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_genpass<>() {
-        cmd("ACL").arg("GENPASS")
+    /// Returns the names of the commands supported by the server, only those
+    /// matching `filter` if given (Redis 7.0+).
+    ///
+    /// ```text
+    /// COMMAND LIST [FILTERBY MODULE <module> | ACLCAT <category> | PATTERN <pattern>]
+    /// ```
+    fn command_list<>(filter: Option<CommandListFilter>) {
+        cmd("COMMAND").arg("LIST").arg(filter)
     }
 
-    /// Generates a 1-to-1024-bits password starting from /dev/urandom if available.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_genpass_bits<>(bits: isize) {
-        cmd("ACL").arg("GENPASS").arg(bits)
+    /// Returns the documentation of the given commands (Redis 7.0+).
+    ///
+    /// The reply is a map from command name to a map of documentation fields.
+    ///
+    /// ```text
+    /// COMMAND DOCS <name> [<name> ...]
+    /// ```
+    fn command_docs<N: ToRedisArgs>(names: N) {
+        cmd("COMMAND").arg("DOCS").arg(names)
     }
 
-    /// Returns the username the current connection is authenticated with.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_whoami<>() {
-        cmd("ACL").arg("WHOAMI")
+    /// Returns details about the given commands, see [`CommandInfo`](crate::CommandInfo).
+    ///
+    /// Unknown commands are returned as nil, use `Vec<Option<CommandInfo>>`
+    /// if some of the names may not exist.
+    ///
+    /// ```text
+    /// COMMAND INFO <name> [<name> ...]
+    /// ```
+    fn command_info<N: ToRedisArgs>(names: N) {
+        cmd("COMMAND").arg("INFO").arg(names)
     }
 
-    /// Shows a list of recent ACL security events
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_log<>(count: isize) {
-        cmd("ACL").arg("LOG").arg(count)
+    /// Returns the keys a full command (name and arguments) would operate on,
+    /// without executing it.
+    ///
+    /// ```text
+    /// COMMAND GETKEYS <command> [<arg> ...]
+    /// ```
+    fn command_getkeys<A: ToRedisArgs>(command_and_args: A) {
+        cmd("COMMAND").arg("GETKEYS").arg(command_and_args)
+    }
 
+    /// Blocks until all the previous write commands of this connection are
+    /// acknowledged by at least `numreplicas` replicas, or until `timeout`
+    /// milliseconds elapse (0 blocks forever). Returns the number of replicas
+    /// that acknowledged the writes.
+    ///
+    /// ```text
+    /// WAIT <numreplicas> <timeout>
+    /// ```
+    fn wait<>(numreplicas: usize, timeout: usize) {
+        cmd("WAIT").arg(numreplicas).arg(timeout)
     }
 
-    /// Clears the ACL log.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_log_reset<>() {
-        cmd("ACL").arg("LOG").arg("RESET")
+    /// Blocks until all the previous write commands of this connection are
+    /// fsynced to the AOF of the local server (if `numlocal` is 1) and of at
+    /// least `numreplicas` replicas, or until `timeout` milliseconds elapse
+    /// (0 blocks forever).
+    ///
+    /// Returns the pair `(local, replicas)` of the number of local servers and
+    /// replicas that acknowledged the writes, for instance as `(u32, u32)`.
+    ///
+    /// This requires Redis 7.2+, which can be checked with
+    /// [`server_version`](Self::server_version).
+    ///
+    /// ```text
+    /// WAITAOF <numlocal> <numreplicas> <timeout>
+    /// ```
+    fn waitaof<>(numlocal: usize, numreplicas: usize, timeout: usize) {
+        cmd("WAITAOF").arg(numlocal).arg(numreplicas).arg(timeout)
     }
 
-    /// Returns a helpful text describing the different subcommands.
-    #[cfg(feature = "acl")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
-    fn acl_help<>() {
-        cmd("ACL").arg("HELP")
+    /// Returns a piece of generative computer art followed by the server
+    /// version, read it as a `String` (Redis 5.0+). As it has no side effects
+    /// and always produces a non-empty reply, it is handy as a smoke test.
+    ///
+    /// ```text
+    /// LOLWUT [VERSION <version>]
+    /// ```
+    fn lolwut<>(version: Option<u8>) {
+        cmd("LOLWUT").arg(version.map(|version| ("VERSION", version)))
     }
 
-    //
-    // geospatial commands
-    //
+    // Sentinel commands
 
-    /// Adds the specified geospatial items to the specified key.
-    ///
-    /// Every member has to be written as a tuple of `(longitude, latitude,
-    /// member_name)`. It can be a single tuple, or a vector of tuples.
+    /// Returns the masters monitored by the sentinel, read them as
+    /// `Vec<SentinelMasterInfo>`, see [`SentinelMasterInfo`](crate::SentinelMasterInfo).
     ///
-    /// `longitude, latitude` can be set using [`redis::geo::Coord`][1].
+    /// ```text
+    /// SENTINEL MASTERS
+    /// ```
+    fn sentinel_masters<>() {
+        cmd("SENTINEL").arg("MASTERS")
+    }
+
+    /// Returns the replicas of the master monitored as `master_name`, read
+    /// them as `Vec<SentinelSlaveInfo>`, see
+    /// [`SentinelSlaveInfo`](crate::SentinelSlaveInfo).
     ///
-    /// [1]: ./geo/struct.Coord.html
+    /// ```text
+    /// SENTINEL SLAVES <master name>
+    /// ```
+    fn sentinel_slaves<>(master_name: &'a str) {
+        cmd("SENTINEL").arg("SLAVES").arg(master_name)
+    }
+
+    /// Returns the other sentinels monitoring the master monitored as
+    /// `master_name`, read them as `Vec<SentinelInfo>`, see
+    /// [`SentinelInfo`](crate::SentinelInfo).
     ///
-    /// Returns the number of elements added to the sorted set, not including
-    /// elements already existing for which the score was updated.
+    /// ```text
+    /// SENTINEL SENTINELS <master name>
+    /// ```
+    fn sentinel_sentinels<>(master_name: &'a str) {
+        cmd("SENTINEL").arg("SENTINELS").arg(master_name)
+    }
+
+    /// Returns the address of the master monitored as `master_name`, read it
+    /// as `Option<(String, u16)>`: the host and port, or `None` if the
+    /// sentinel doesn't know the master. During a failover this is the
+    /// address of the promoted replica.
     ///
-    /// # Example
+    /// ```text
+    /// SENTINEL GET-MASTER-ADDR-BY-NAME <master name>
+    /// ```
+    fn sentinel_get_master_addr_by_name<>(master_name: &'a str) {
+        cmd("SENTINEL").arg("GET-MASTER-ADDR-BY-NAME").arg(master_name)
+    }
+
+    /// Forces a failover of the master monitored as `master_name`, without
+    /// asking the other sentinels for agreement.
     ///
-    /// ```rust,no_run
+    /// ```text
+    /// SENTINEL FAILOVER <master name>
+    /// ```
+    fn sentinel_failover<>(master_name: &'a str) {
+        cmd("SENTINEL").arg("FAILOVER").arg(master_name)
+    }
+
+    /// Resets the state of the masters whose name matches the glob-style
+    /// `pattern`, and returns the number of masters reset.
+    ///
+    /// ```text
+    /// SENTINEL RESET <pattern>
+    /// ```
+    fn sentinel_reset<>(pattern: &'a str) {
+        cmd("SENTINEL").arg("RESET").arg(pattern)
+    }
+
+    /// Checks whether the sentinels monitoring `master_name` can reach the
+    /// quorum needed to fail it over, and the majority needed to authorize
+    /// the failover. Read the reply as `()`: when they can't, the command
+    /// fails with a `NOQUORUM` error (see [`RedisError::code`](crate::RedisError::code))
+    /// whose detail is the sentinel's explanation.
+    ///
+    /// ```text
+    /// SENTINEL CKQUORUM <master name>
+    /// ```
+    fn sentinel_ckquorum<>(master_name: &'a str) {
+        cmd("SENTINEL").arg("CKQUORUM").arg(master_name)
+    }
+
+    // Cluster commands
+
+    /// Returns the hash slot of a key, as computed by the server. See
+    /// [`cluster::key_hash_slot`](crate::cluster::key_hash_slot) to compute it
+    /// locally.
+    ///
+    /// ```text
+    /// CLUSTER KEYSLOT <key>
+    /// ```
+    fn cluster_keyslot<K: ToRedisArgs>(key: K) {
+        cmd("CLUSTER").arg("KEYSLOT").arg(key)
+    }
+
+    /// Returns the mapping of hash slot ranges to nodes, as a list of
+    /// [`ClusterSlot`](crate::ClusterSlot).
+    ///
+    /// ```text
+    /// CLUSTER SLOTS
+    /// ```
+    fn cluster_slots<>() {
+        cmd("CLUSTER").arg("SLOTS")
+    }
+
+    /// Returns the shards of the cluster, as a list of
+    /// [`ClusterShard`](crate::ClusterShard) (Redis 7.0+). This supersedes
+    /// `CLUSTER SLOTS`.
+    ///
+    /// ```text
+    /// CLUSTER SHARDS
+    /// ```
+    fn cluster_shards<>() {
+        cmd("CLUSTER").arg("SHARDS")
+    }
+
+    /// Returns up to `count` key names stored in the given hash slot of the
+    /// node the command is sent to.
+    ///
+    /// ```text
+    /// CLUSTER GETKEYSINSLOT <slot> <count>
+    /// ```
+    fn cluster_getkeysinslot<>(slot: u16, count: usize) {
+        cmd("CLUSTER").arg("GETKEYSINSLOT").arg(slot).arg(count)
+    }
+
+    /// Returns the number of keys stored in the given hash slot of the node
+    /// the command is sent to.
+    ///
+    /// ```text
+    /// CLUSTER COUNTKEYSINSLOT <slot>
+    /// ```
+    fn cluster_countkeysinslot<>(slot: u16) {
+        cmd("CLUSTER").arg("COUNTKEYSINSLOT").arg(slot)
+    }
+
+    /// Marks a hash slot of the node the command is sent to as being imported
+    /// from the node with the given id, the first step of migrating the slot
+    /// to this node.
+    ///
+    /// ```text
+    /// CLUSTER SETSLOT <slot> IMPORTING <node-id>
+    /// ```
+    fn cluster_setslot_importing<>(slot: u16, node_id: &'a str) {
+        cmd("CLUSTER").arg("SETSLOT").arg(slot).arg("IMPORTING").arg(node_id)
+    }
+
+    /// Marks a hash slot of the node the command is sent to as being migrated
+    /// to the node with the given id, so that requests for missing keys are
+    /// redirected there with `ASK`.
+    ///
+    /// ```text
+    /// CLUSTER SETSLOT <slot> MIGRATING <node-id>
+    /// ```
+    fn cluster_setslot_migrating<>(slot: u16, node_id: &'a str) {
+        cmd("CLUSTER").arg("SETSLOT").arg(slot).arg("MIGRATING").arg(node_id)
+    }
+
+    /// Assigns a hash slot to the node with the given id, the last step of a
+    /// migration, to be sent to both the source and the target nodes.
+    ///
+    /// ```text
+    /// CLUSTER SETSLOT <slot> NODE <node-id>
+    /// ```
+    fn cluster_setslot_node<>(slot: u16, node_id: &'a str) {
+        cmd("CLUSTER").arg("SETSLOT").arg(slot).arg("NODE").arg(node_id)
+    }
+
+    /// Clears the importing or migrating state of a hash slot of the node the
+    /// command is sent to, for instance to abort a migration.
+    ///
+    /// ```text
+    /// CLUSTER SETSLOT <slot> STABLE
+    /// ```
+    fn cluster_setslot_stable<>(slot: u16) {
+        cmd("CLUSTER").arg("SETSLOT").arg(slot).arg("STABLE")
+    }
+
+    // Function commands
+
+    /// Returns the function currently running, if any, and the number of
+    /// libraries and functions per engine, as a
+    /// [`FunctionStats`](crate::FunctionStats) (Redis 7.0+).
+    ///
+    /// ```text
+    /// FUNCTION STATS
+    /// ```
+    fn function_stats<>() {
+        cmd("FUNCTION").arg("STATS")
+    }
+
+    /// Returns a serialized payload of all the loaded function libraries,
+    /// to be loaded back with [`function_restore`](#method.function_restore)
+    /// (Redis 7.0+).
+    ///
+    /// ```text
+    /// FUNCTION DUMP
+    /// ```
+    fn function_dump<>() {
+        cmd("FUNCTION").arg("DUMP")
+    }
+
+    /// Loads the function libraries from a payload returned by
+    /// [`function_dump`](#method.function_dump), resolving conflicts with the
+    /// libraries already loaded according to `policy` (Redis 7.0+).
+    ///
+    /// ```text
+    /// FUNCTION RESTORE <payload> <FLUSH | APPEND | REPLACE>
+    /// ```
+    fn function_restore<>(payload: &'a [u8], policy: RestorePolicy) {
+        cmd("FUNCTION").arg("RESTORE").arg(payload).arg(policy)
+    }
+
+    // Debug commands
+
+    /// Makes the server change its replication ID, as if it had been
+    /// promoted, so that replicas need a full resynchronization.
+    ///
+    /// `DEBUG` has to be enabled on Redis 7.0+ with the `enable-debug-command`
+    /// configuration option.
+    ///
+    /// ```text
+    /// DEBUG CHANGE-REPL-ID
+    /// ```
+    #[cfg(feature = "test-support")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-support")))]
+    fn debug_change_repl_id<>() {
+        cmd("DEBUG").arg("CHANGE-REPL-ID")
+    }
+
+    /// Enables or disables the active expiration of keys, so that expired
+    /// keys are only removed when they are accessed. This makes tests on
+    /// keys with a time to live deterministic.
+    ///
+    /// ```text
+    /// DEBUG SET-ACTIVE-EXPIRE <0|1>
+    /// ```
+    #[cfg(feature = "test-support")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-support")))]
+    fn debug_set_active_expire<>(enabled: bool) {
+        cmd("DEBUG").arg("SET-ACTIVE-EXPIRE").arg(enabled)
+    }
+
+    /// Sets the size in bytes above which list elements are stored in their
+    /// own plain quicklist node instead of being packed with others (Redis
+    /// 7.0+). The default is 1GB, lowering it lets tests exercise plain nodes
+    /// with small elements.
+    ///
+    /// ```text
+    /// DEBUG QUICKLIST-PACKED-THRESHOLD <size>
+    /// ```
+    #[cfg(feature = "test-support")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-support")))]
+    fn debug_quicklist_packed_threshold<>(size: usize) {
+        cmd("DEBUG").arg("QUICKLIST-PACKED-THRESHOLD").arg(size)
+    }
+
+    // ACL commands
+
+    /// When Redis is configured to use an ACL file (with the aclfile
+    /// configuration option), this command will reload the ACLs from the file,
+    /// replacing all the current ACL rules with the ones defined in the file.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_load<>() {
+        cmd("ACL").arg("LOAD")
+    }
+
+    /// When Redis is configured to use an ACL file (with the aclfile
+    /// configuration option), this command will save the currently defined
+    /// ACLs from the server memory to the ACL file.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_save<>() {
+        cmd("ACL").arg("SAVE")
+    }
+
+    /// Shows the currently active ACL rules in the Redis server.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_list<>() {
+        cmd("ACL").arg("LIST")
+    }
+
+    /// Shows a list of all the usernames of the currently configured users in
+    /// the Redis ACL system.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_users<>() {
+        cmd("ACL").arg("USERS")
+    }
+
+    /// Returns all the rules defined for an existing ACL user.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_getuser<K: ToRedisArgs>(username: K) {
+        cmd("ACL").arg("GETUSER").arg(username)
+    }
+
+    /// Creates an ACL user without any privilege.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_setuser<K: ToRedisArgs>(username: K) {
+        cmd("ACL").arg("SETUSER").arg(username)
+    }
+
+    /// Creates an ACL user with the specified rules or modify the rules of
+    /// an existing user.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_setuser_rules<K: ToRedisArgs>(username: K, rules: &'a [acl::Rule]) {
+        cmd("ACL").arg("SETUSER").arg(username).arg(rules)
+    }
+
+    /// Delete all the specified ACL users and terminate all the connections
+    /// that are authenticated with such users.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_deluser<K: ToRedisArgs>(usernames: &'a [K]) {
+        cmd("ACL").arg("DELUSER").arg(usernames)
+    }
+
+    /// Shows the available ACL categories.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_cat<>() {
+        cmd("ACL").arg("CAT")
+    }
+
+    /// Shows all the Redis commands in the specified category.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_cat_categoryname<K: ToRedisArgs>(categoryname: K) {
+        cmd("ACL").arg("CAT").arg(categoryname)
+    }
+
+    /// Generates a 256-bits password starting from /dev/urandom if available.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_genpass<>() {
+        cmd("ACL").arg("GENPASS")
+    }
+
+    /// Generates a 1-to-1024-bits password starting from /dev/urandom if available.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_genpass_bits<>(bits: isize) {
+        cmd("ACL").arg("GENPASS").arg(bits)
+    }
+
+    /// Returns the username the current connection is authenticated with.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_whoami<>() {
+        cmd("ACL").arg("WHOAMI")
+    }
+
+    /// Shows a list of the `count` most recent ACL security events, the most
+    /// recent first. Read the reply as `Vec<AclLogEntry>`, see
+    /// [`AclLogEntry`](crate::acl::AclLogEntry).
+    ///
+    /// ```text
+    /// ACL LOG <count>
+    /// ```
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_log<>(count: isize) {
+        cmd("ACL").arg("LOG").arg(count)
+    }
+
+    /// Same as [`acl_log`](Self::acl_log), leaving the number of events to the
+    /// server (10 by default) when `count` is `None`.
+    ///
+    /// ```text
+    /// ACL LOG [<count>]
+    /// ```
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_log_entries<>(count: Option<usize>) {
+        cmd("ACL").arg("LOG").arg(count)
+    }
+
+    /// Clears the ACL log.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_log_reset<>() {
+        cmd("ACL").arg("LOG").arg("RESET")
+    }
+
+    /// Returns a helpful text describing the different subcommands.
+    #[cfg(feature = "acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
+    fn acl_help<>() {
+        cmd("ACL").arg("HELP")
+    }
+
+    //
+    // geospatial commands
+    //
+
+    /// Adds the specified geospatial items to the specified key.
+    ///
+    /// Every member has to be written as a tuple of `(longitude, latitude,
+    /// member_name)`. It can be a single tuple, or a vector of tuples.
+    ///
+    /// `longitude, latitude` can be set using [`redis::geo::Coord`][1].
+    ///
+    /// [1]: ./geo/struct.Coord.html
+    ///
+    /// Returns the number of elements added to the sorted set, not including
+    /// elements already existing for which the score was updated.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
     /// use redis::{Commands, Connection, RedisResult};
     /// use redis::geo::Coord;
     ///
@@ -1176,6 +1931,25 @@ implement_commands! {
 
     /// Retrieve members selected by distance with the center of `member`. The
     /// member itself is always contained in the results.
+    ///
+    /// All options of `GEORADIUSBYMEMBER` are set through [`redis::geo::RadiusOptions`][1].
+    ///
+    /// [1]: ./geo/struct.RadiusOptions.html
+    ///
+    /// ```rust,no_run
+    /// use redis::Commands;
+    /// use redis::geo::{RadiusOptions, RadiusOrder, RadiusSearchResult, Unit};
+    ///
+    /// fn closest(con: &mut redis::Connection) -> Vec<RadiusSearchResult> {
+    ///     let opts = RadiusOptions::default()
+    ///         .with_coord()
+    ///         .with_dist()
+    ///         .with_hash()
+    ///         .order(RadiusOrder::Asc)
+    ///         .limit(3);
+    ///     con.geo_radius_by_member("my_gis", "Palermo", 200.0, Unit::Kilometers, opts).unwrap()
+    /// }
+    /// ```
     #[cfg(feature = "geospatial")]
     #[cfg_attr(docsrs, doc(cfg(feature = "geospatial")))]
     fn geo_radius_by_member<K: ToRedisArgs, M: ToRedisArgs>(
@@ -1199,6 +1973,9 @@ implement_commands! {
 
     /// Ack pending stream messages checked out by a consumer.
     ///
+    /// Replies with the number of messages acknowledged, which leaves out ids
+    /// that were not pending in the group.
+    ///
     /// ```text
     /// XACK <key> <group> <id> <id> ... <id>
     /// ```
@@ -1468,7 +2245,7 @@ implement_commands! {
     /// Destroy an existing consumer `group` for a given stream `key`
     ///
     /// ```text
-    /// XGROUP SETID <key> <groupname> <id or $>
+    /// XGROUP DESTROY <key> <groupname>
     /// ```
     #[cfg(feature = "streams")]
     #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
@@ -1479,21 +2256,44 @@ implement_commands! {
         cmd("XGROUP").arg("DESTROY").arg(key).arg(group)
     }
 
-    /// This deletes a `consumer` from an existing consumer `group`
+    /// This creates a `consumer` explicitly (vs implicit via XREADGROUP)
     /// for given stream `key.
     ///
+    /// The return value is either a 0 or a 1 for the number of consumers created
+    /// 0 means the consumer already exists
+    ///
     /// ```text
-    /// XGROUP DELCONSUMER <key> <groupname> <consumername>
+    /// XGROUP CREATECONSUMER <key> <groupname> <consumername>
     /// ```
     #[cfg(feature = "streams")]
     #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
-    fn xgroup_delconsumer<K: ToRedisArgs, G: ToRedisArgs, C: ToRedisArgs>(
+    fn xgroup_createconsumer<K: ToRedisArgs, G: ToRedisArgs, C: ToRedisArgs>(
         key: K,
         group: G,
         consumer: C
     ) {
         cmd("XGROUP")
-            .arg("DELCONSUMER")
+            .arg("CREATECONSUMER")
+            .arg(key)
+            .arg(group)
+            .arg(consumer)
+    }
+
+    /// This deletes a `consumer` from an existing consumer `group`
+    /// for given stream `key.
+    ///
+    /// ```text
+    /// XGROUP DELCONSUMER <key> <groupname> <consumername>
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xgroup_delconsumer<K: ToRedisArgs, G: ToRedisArgs, C: ToRedisArgs>(
+        key: K,
+        group: G,
+        consumer: C
+    ) {
+        cmd("XGROUP")
+            .arg("DELCONSUMER")
             .arg(key)
             .arg(group)
             .arg(consumer)
@@ -1790,6 +2590,38 @@ implement_commands! {
         .arg(ids)
     }
 
+    /// Read a list of `id`s for each stream `key` as `consumer` of `group`,
+    /// without adding the delivered entries to the group's pending entries
+    /// list (PEL).
+    ///
+    /// This gives at-most-once delivery: an entry is considered acknowledged
+    /// as soon as it is read, so it is lost if the consumer fails before
+    /// processing it, and it never needs an `XACK`. Use `xread_options` when
+    /// entries must be redelivered after a failure.
+    ///
+    /// ```text
+    /// XREADGROUP GROUP group-name consumer-name NOACK
+    ///     STREAMS key_1 key_2 ... key_N
+    ///     ID_1 ID_2 ... ID_N
+    /// ```
+    #[cfg(feature = "streams")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "streams")))]
+    fn xread_group_noack<G: ToRedisArgs, C: ToRedisArgs, K: ToRedisArgs, I: ToRedisArgs>(
+        group: G,
+        consumer: C,
+        keys: &'a [K],
+        ids: &'a [I]
+    ) {
+        cmd("XREADGROUP")
+            .arg("GROUP")
+            .arg(group)
+            .arg(consumer)
+            .arg("NOACK")
+            .arg("STREAMS")
+            .arg(keys)
+            .arg(ids)
+    }
+
     /// This is the reverse version of `xrange`.
     /// The same rules apply for `start` and `end` here.
     ///
@@ -2037,6 +2869,344 @@ impl ToRedisArgs for LposOptions {
     }
 }
 
+/// Options for the unified [ZRANGE](https://redis.io/commands/zrange) command
+/// (Redis 6.2+)
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, ZRangeOptions};
+/// fn top_scores(con: &mut redis::Connection, page: isize) -> RedisResult<Vec<(String, f64)>> {
+///     // Highest scores first, ten per page
+///     let opts = ZRangeOptions::default()
+///         .by_score()
+///         .rev()
+///         .limit(page * 10, 10)
+///         .with_scores();
+///     con.zrange_options("high_scores", "+inf", "-inf", opts)
+/// }
+/// ```
+#[derive(Default)]
+pub struct ZRangeOptions {
+    by_score: bool,
+    by_lex: bool,
+    rev: bool,
+    limit: Option<(isize, isize)>,
+    with_scores: bool,
+}
+
+impl ZRangeOptions {
+    /// Select the members by score, `start` and `stop` being the score range.
+    pub fn by_score(mut self) -> Self {
+        self.by_score = true;
+        self.by_lex = false;
+        self
+    }
+
+    /// Select the members by lexicographical range, `start` and `stop` being
+    /// range items such as `[a` or `(b`.
+    pub fn by_lex(mut self) -> Self {
+        self.by_lex = true;
+        self.by_score = false;
+        self
+    }
+
+    /// Return the members from the highest to the lowest. The range then goes
+    /// from `start` down to `stop`.
+    pub fn rev(mut self) -> Self {
+        self.rev = true;
+        self
+    }
+
+    /// Return at most `count` members, skipping the first `offset` ones. Only
+    /// valid with [`by_score`](Self::by_score) or [`by_lex`](Self::by_lex).
+    pub fn limit(mut self, offset: isize, count: isize) -> Self {
+        self.limit = Some((offset, count));
+        self
+    }
+
+    /// Return the score of each member too.
+    pub fn with_scores(mut self) -> Self {
+        self.with_scores = true;
+        self
+    }
+}
+
+impl ToRedisArgs for ZRangeOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if self.by_score {
+            out.write_arg(b"BYSCORE");
+        }
+        if self.by_lex {
+            out.write_arg(b"BYLEX");
+        }
+        if self.rev {
+            out.write_arg(b"REV");
+        }
+        if let Some((offset, count)) = self.limit {
+            out.write_arg(b"LIMIT");
+            out.write_arg_fmt(offset);
+            out.write_arg_fmt(count);
+        }
+        if self.with_scores {
+            out.write_arg(b"WITHSCORES");
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the [ZADD](https://redis.io/commands/zadd) command
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, ZaddOptions};
+/// fn raise_high_score(con: &mut redis::Connection, player: &str, score: u64) -> RedisResult<bool> {
+///     // Only ever raise the score, and count the member as changed when it was.
+///     let opts = ZaddOptions::default().gt().ch();
+///     con.zadd_options("high_scores", player, score, opts)
+/// }
+/// ```
+#[derive(Default)]
+pub struct ZaddOptions {
+    nx: bool,
+    xx: bool,
+    gt: bool,
+    lt: bool,
+    ch: bool,
+    incr: bool,
+}
+
+impl ZaddOptions {
+    /// Only add new members, never update existing ones.
+    pub fn nx(mut self) -> Self {
+        self.nx = true;
+        self
+    }
+
+    /// Only update existing members, never add new ones.
+    pub fn xx(mut self) -> Self {
+        self.xx = true;
+        self
+    }
+
+    /// Only update existing members if the new score is greater (Redis 6.2+).
+    pub fn gt(mut self) -> Self {
+        self.gt = true;
+        self
+    }
+
+    /// Only update existing members if the new score is less (Redis 6.2+).
+    pub fn lt(mut self) -> Self {
+        self.lt = true;
+        self
+    }
+
+    /// Count the changed members in the reply, not only the added ones.
+    pub fn ch(mut self) -> Self {
+        self.ch = true;
+        self
+    }
+
+    /// Increment the score of the member instead of setting it, replying with
+    /// the new score like `ZINCRBY`.
+    pub fn incr(mut self) -> Self {
+        self.incr = true;
+        self
+    }
+}
+
+impl ToRedisArgs for ZaddOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if self.nx {
+            out.write_arg(b"NX");
+        }
+        if self.xx {
+            out.write_arg(b"XX");
+        }
+        if self.gt {
+            out.write_arg(b"GT");
+        }
+        if self.lt {
+            out.write_arg(b"LT");
+        }
+        if self.ch {
+            out.write_arg(b"CH");
+        }
+        if self.incr {
+            out.write_arg(b"INCR");
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the [MIGRATE](https://redis.io/commands/migrate) command
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use redis::{Commands, MigrateOptions, RedisResult};
+/// fn move_users(con: &mut redis::Connection) -> RedisResult<String> {
+///     let opts = MigrateOptions::default()
+///         .replace()
+///         .auth("secret")
+///         .keys(&["user:1", "user:2"]);
+///     con.migrate("10.0.0.2", 6379, "", 0, 5000, opts)
+/// }
+/// ```
+#[derive(Default)]
+pub struct MigrateOptions {
+    copy: bool,
+    replace: bool,
+    username: Option<Vec<Vec<u8>>>,
+    password: Option<Vec<Vec<u8>>>,
+    keys: Option<Vec<Vec<u8>>>,
+}
+
+impl MigrateOptions {
+    /// Do not remove the keys from the local instance.
+    pub fn copy(mut self) -> Self {
+        self.copy = true;
+        self
+    }
+
+    /// Replace existing keys on the remote instance.
+    pub fn replace(mut self) -> Self {
+        self.replace = true;
+        self
+    }
+
+    /// Authenticate with the given password to the remote instance.
+    pub fn auth<P: ToRedisArgs>(mut self, password: P) -> Self {
+        self.username = None;
+        self.password = Some(password.to_redis_args());
+        self
+    }
+
+    /// Authenticate with the given username and password to the remote
+    /// instance (Redis 6.0+).
+    pub fn auth2<U: ToRedisArgs, P: ToRedisArgs>(mut self, username: U, password: P) -> Self {
+        self.username = Some(username.to_redis_args());
+        self.password = Some(password.to_redis_args());
+        self
+    }
+
+    /// Transfer all the given keys. The `key` argument of the command must be
+    /// an empty string then.
+    pub fn keys<K: ToRedisArgs>(mut self, keys: K) -> Self {
+        self.keys = Some(keys.to_redis_args());
+        self
+    }
+}
+
+impl ToRedisArgs for MigrateOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if self.copy {
+            out.write_arg(b"COPY");
+        }
+
+        if self.replace {
+            out.write_arg(b"REPLACE");
+        }
+
+        if let Some(ref password) = self.password {
+            match self.username {
+                Some(ref username) => {
+                    out.write_arg(b"AUTH2");
+                    for arg in username {
+                        out.write_arg(arg);
+                    }
+                }
+                None => out.write_arg(b"AUTH"),
+            }
+            for arg in password {
+                out.write_arg(arg);
+            }
+        }
+
+        if let Some(ref keys) = self.keys {
+            out.write_arg(b"KEYS");
+            for arg in keys {
+                out.write_arg(arg);
+            }
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Options for the `MATCH` and `COUNT` arguments of the
+/// [HSCAN](https://redis.io/commands/hscan) command
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use redis::{Commands, RedisResult, ScanOptions};
+/// fn user_fields(con: &mut redis::Connection, key: &str) -> RedisResult<Vec<(String, String)>> {
+///     let opts = ScanOptions::default().with_pattern("user:*").with_count(100);
+///     Ok(con.hscan_options(key, opts)?.collect())
+/// }
+/// ```
+#[derive(Default)]
+pub struct ScanOptions {
+    pattern: Option<Vec<Vec<u8>>>,
+    count: Option<usize>,
+}
+
+impl ScanOptions {
+    /// Only return elements matching the glob-style pattern.
+    pub fn with_pattern<P: ToRedisArgs>(mut self, pattern: P) -> Self {
+        self.pattern = Some(pattern.to_redis_args());
+        self
+    }
+
+    /// Hint how many elements should be returned per call to the server.
+    pub fn with_count(mut self, n: usize) -> Self {
+        self.count = Some(n);
+        self
+    }
+}
+
+impl ToRedisArgs for ScanOptions {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        if let Some(ref pattern) = self.pattern {
+            out.write_arg(b"MATCH");
+            for arg in pattern {
+                out.write_arg(arg);
+            }
+        }
+
+        if let Some(n) = self.count {
+            out.write_arg(b"COUNT");
+            out.write_arg_fmt(n);
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
 /// Enum for the LEFT | RIGHT args used by some commands
 pub enum Direction {
     /// Targets the first element (head) of the list
@@ -2057,3 +3227,159 @@ impl ToRedisArgs for Direction {
         out.write_arg(s);
     }
 }
+
+/// The MIN | MAX end of a sorted set popped from by
+/// [`ZMPOP`](https://redis.io/commands/zmpop) and
+/// [`BZMPOP`](https://redis.io/commands/bzmpop).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Pops the members with the lowest scores
+    Min,
+    /// Pops the members with the highest scores
+    Max,
+}
+
+impl ToRedisArgs for SortOrder {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s: &[u8] = match self {
+            SortOrder::Min => b"MIN",
+            SortOrder::Max => b"MAX",
+        };
+        out.write_arg(s);
+    }
+}
+
+/// The LEFT | RIGHT end popped from by [`LMPOP`](https://redis.io/commands/lmpop)
+/// and [`BLMPOP`](https://redis.io/commands/blmpop).
+///
+/// This is the same type as [`Direction`], so either name can be used.
+pub type PopDirection = Direction;
+
+/// The BYTE | BIT unit of the range given to
+/// [`BITCOUNT`](https://redis.io/commands/bitcount) and
+/// [`BITPOS`](https://redis.io/commands/bitpos).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeMode {
+    /// The range is given in bytes
+    Byte,
+    /// The range is given in bits
+    Bit,
+}
+
+impl ToRedisArgs for RangeMode {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s: &[u8] = match self {
+            RangeMode::Byte => b"BYTE",
+            RangeMode::Bit => b"BIT",
+        };
+        out.write_arg(s);
+    }
+}
+
+/// Enum for the WRITE | ALL args used by [`CLIENT PAUSE`](https://redis.io/commands/client-pause)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClientPauseMode {
+    /// Only pauses clients sending write commands
+    Write,
+    /// Pauses all clients
+    All,
+}
+
+impl ToRedisArgs for ClientPauseMode {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s: &[u8] = match self {
+            ClientPauseMode::Write => b"WRITE",
+            ClientPauseMode::All => b"ALL",
+        };
+        out.write_arg(s);
+    }
+}
+
+/// Enum for the policies of [`FUNCTION RESTORE`](https://redis.io/commands/function-restore)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestorePolicy {
+    /// Deletes all existing libraries before restoring the payload
+    Flush,
+    /// Appends the restored libraries, failing on name collisions
+    Append,
+    /// Appends the restored libraries, replacing existing ones on name collisions
+    Replace,
+}
+
+impl ToRedisArgs for RestorePolicy {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s: &[u8] = match self {
+            RestorePolicy::Flush => b"FLUSH",
+            RestorePolicy::Append => b"APPEND",
+            RestorePolicy::Replace => b"REPLACE",
+        };
+        out.write_arg(s);
+    }
+}
+
+/// Enum for the client types used by [`CLIENT LIST`](https://redis.io/commands/client-list)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClientType {
+    /// Regular clients
+    Normal,
+    /// The connection to the master, on a replica
+    Master,
+    /// Replicas connected to this server
+    Replica,
+    /// Clients subscribed to at least one channel or pattern
+    PubSub,
+}
+
+impl ToRedisArgs for ClientType {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let s: &[u8] = match self {
+            ClientType::Normal => b"NORMAL",
+            ClientType::Master => b"MASTER",
+            ClientType::Replica => b"REPLICA",
+            ClientType::PubSub => b"PUBSUB",
+        };
+        out.write_arg(s);
+    }
+}
+
+/// Enum for the filters of [`COMMAND LIST`](https://redis.io/commands/command-list)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandListFilter {
+    /// Commands of the given module
+    Module(String),
+    /// Commands in the given ACL category, such as `dangerous`
+    AclCat(String),
+    /// Commands whose name matches the given glob-style pattern
+    Pattern(String),
+}
+
+impl ToRedisArgs for CommandListFilter {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        let (s, value): (&[u8], &str) = match self {
+            CommandListFilter::Module(module) => (b"MODULE", module),
+            CommandListFilter::AclCat(category) => (b"ACLCAT", category),
+            CommandListFilter::Pattern(pattern) => (b"PATTERN", pattern),
+        };
+        out.write_arg(b"FILTERBY");
+        out.write_arg(s);
+        out.write_arg(value.as_bytes());
+    }
+}
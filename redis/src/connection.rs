@@ -103,8 +103,71 @@ pub struct ConnectionInfo {
     pub redis: RedisConnectionInfo,
 }
 
+impl ConnectionInfo {
+    /// Reads the connection information from the `{prefix}_HOST`,
+    /// `{prefix}_PORT`, `{prefix}_DB`, `{prefix}_USERNAME` and
+    /// `{prefix}_PASSWORD` environment variables.
+    ///
+    /// Unset variables fall back to `localhost`, port 6379, database 0 and
+    /// no authentication. Values that don't parse are reported as
+    /// [`ErrorKind::InvalidClientConfig`].
+    ///
+    /// ```rust,no_run
+    /// // Reads REDIS_HOST, REDIS_PORT, ...
+    /// let info = redis::ConnectionInfo::from_env("REDIS")?;
+    /// let client = redis::Client::open(info)?;
+    /// # Ok::<(), redis::RedisError>(())
+    /// ```
+    pub fn from_env(prefix: &str) -> RedisResult<ConnectionInfo> {
+        let var = |name: &str| -> RedisResult<Option<String>> {
+            match std::env::var(format!("{prefix}_{name}")) {
+                Ok(value) => Ok(Some(value)),
+                Err(std::env::VarError::NotPresent) => Ok(None),
+                Err(std::env::VarError::NotUnicode(_)) => Err((
+                    ErrorKind::InvalidClientConfig,
+                    "Environment variable is not valid unicode",
+                    format!("{prefix}_{name}"),
+                )
+                    .into()),
+            }
+        };
+
+        let host = var("HOST")?.unwrap_or_else(|| "localhost".to_string());
+        let port = match var("PORT")? {
+            Some(port) => port.parse().map_err(|_| {
+                RedisError::from((ErrorKind::InvalidClientConfig, "Invalid port", port))
+            })?,
+            None => DEFAULT_PORT,
+        };
+        let db = match var("DB")? {
+            Some(db) => db.parse().map_err(|_| {
+                RedisError::from((
+                    ErrorKind::InvalidClientConfig,
+                    "Invalid database number",
+                    db,
+                ))
+            })?,
+            None => 0,
+        };
+
+        Ok(ConnectionInfo {
+            addr: ConnectionAddr::Tcp(host, port),
+            redis: RedisConnectionInfo {
+                db,
+                username: var("USERNAME")?,
+                password: var("PASSWORD")?,
+                ..Default::default()
+            },
+        })
+    }
+}
+
 /// Redis specific/connection independent information used to establish a connection to redis.
+///
+/// The struct is non-exhaustive so that options can be added without breaking
+/// code that builds it: start from [`Default::default`] and set the fields.
 #[derive(Clone, Debug, Default)]
+#[non_exhaustive]
 pub struct RedisConnectionInfo {
     /// The database number to use.  This is usually `0`.
     pub db: i64,
@@ -112,6 +175,30 @@ pub struct RedisConnectionInfo {
     pub username: Option<String>,
     /// Optionally a password that should be used for connection.
     pub password: Option<String>,
+    /// Switch the connection to the RESP3 protocol with `HELLO 3` (Redis 6.0+).
+    ///
+    /// Maps and sets are then decoded as [`Value::Map`] and [`Value::Set`]
+    /// instead of flat bulk responses.
+    pub use_resp3: bool,
+}
+
+/// The version of the protocol a connection speaks with the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtocolVersion {
+    /// The protocol all server versions support.
+    RESP2,
+    /// The protocol introduced in Redis 6.0, negotiated with `HELLO 3`.
+    RESP3,
+}
+
+impl RedisConnectionInfo {
+    pub(crate) fn protocol(&self) -> ProtocolVersion {
+        if self.use_resp3 {
+            ProtocolVersion::RESP3
+        } else {
+            ProtocolVersion::RESP2
+        }
+    }
 }
 
 impl FromStr for ConnectionInfo {
@@ -249,6 +336,7 @@ fn url_to_tcp_connection_info(url: url::Url) -> RedisResult<ConnectionInfo> {
                 },
                 None => None,
             },
+            use_resp3: false,
         },
     })
 }
@@ -271,6 +359,7 @@ fn url_to_unix_connection_info(url: url::Url) -> RedisResult<ConnectionInfo> {
             },
             username: query.get("user").map(|username| username.to_string()),
             password: query.get("pass").map(|password| password.to_string()),
+            use_resp3: false,
         },
     })
 }
@@ -301,6 +390,35 @@ struct TcpConnection {
     open: bool,
 }
 
+/// A TLS client certificate and its private key, used to authenticate to
+/// servers that require mutual TLS.
+#[cfg(feature = "tls")]
+#[derive(Clone)]
+pub struct RedisIdentity(native_tls::Identity);
+
+#[cfg(feature = "tls")]
+impl RedisIdentity {
+    /// Parses a DER-formatted PKCS #12 archive, decrypting it with `password`.
+    pub fn from_pkcs12(der: &[u8], password: &str) -> RedisResult<RedisIdentity> {
+        Ok(RedisIdentity(native_tls::Identity::from_pkcs12(
+            der, password,
+        )?))
+    }
+
+    /// Parses a chain of PEM-encoded X509 certificates together with a
+    /// PEM-encoded PKCS #8 private key.
+    pub fn from_pkcs8(pem: &[u8], key: &[u8]) -> RedisResult<RedisIdentity> {
+        Ok(RedisIdentity(native_tls::Identity::from_pkcs8(pem, key)?))
+    }
+}
+
+#[cfg(feature = "tls")]
+impl fmt::Debug for RedisIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RedisIdentity").finish_non_exhaustive()
+    }
+}
+
 #[cfg(feature = "tls")]
 struct TcpTlsConnection {
     reader: TlsStream<TcpStream>,
@@ -326,6 +444,7 @@ pub struct Connection {
     con: ActualConnection,
     parser: Parser,
     db: i64,
+    protocol: ProtocolVersion,
 
     /// Flag indicating whether the connection was left in the PubSub state after dropping `PubSub`.
     ///
@@ -392,60 +511,7 @@ impl ActualConnection {
                 ref host,
                 port,
                 insecure,
-            } => {
-                let tls_connector = if insecure {
-                    TlsConnector::builder()
-                        .danger_accept_invalid_certs(true)
-                        .danger_accept_invalid_hostnames(true)
-                        .use_sni(false)
-                        .build()?
-                } else {
-                    TlsConnector::new()?
-                };
-                let addr = (host.as_str(), port);
-                let tls = match timeout {
-                    None => {
-                        let tcp = TcpStream::connect(addr)?;
-                        match tls_connector.connect(host, tcp) {
-                            Ok(res) => res,
-                            Err(e) => {
-                                fail!((ErrorKind::IoError, "SSL Handshake error", e.to_string()));
-                            }
-                        }
-                    }
-                    Some(timeout) => {
-                        let mut tcp = None;
-                        let mut last_error = None;
-                        for addr in (host.as_str(), port).to_socket_addrs()? {
-                            match TcpStream::connect_timeout(&addr, timeout) {
-                                Ok(l) => {
-                                    tcp = Some(l);
-                                    break;
-                                }
-                                Err(e) => {
-                                    last_error = Some(e);
-                                }
-                            };
-                        }
-                        match (tcp, last_error) {
-                            (Some(tcp), _) => tls_connector.connect(host, tcp).unwrap(),
-                            (None, Some(e)) => {
-                                fail!(e);
-                            }
-                            (None, None) => {
-                                fail!((
-                                    ErrorKind::InvalidClientConfig,
-                                    "could not resolve to any addresses"
-                                ));
-                            }
-                        }
-                    }
-                };
-                ActualConnection::TcpTls(Box::new(TcpTlsConnection {
-                    reader: tls,
-                    open: true,
-                }))
-            }
+            } => ActualConnection::new_tls(host, port, insecure, None, timeout)?,
             #[cfg(not(feature = "tls"))]
             ConnectionAddr::TcpTls { .. } => {
                 fail!((
@@ -469,6 +535,75 @@ impl ActualConnection {
         })
     }
 
+    #[cfg(feature = "tls")]
+    fn new_tls(
+        host: &str,
+        port: u16,
+        insecure: bool,
+        identity: Option<&RedisIdentity>,
+        timeout: Option<Duration>,
+    ) -> RedisResult<ActualConnection> {
+        let mut builder = TlsConnector::builder();
+        if insecure {
+            builder
+                .danger_accept_invalid_certs(true)
+                .danger_accept_invalid_hostnames(true)
+                .use_sni(false);
+        }
+        if let Some(identity) = identity {
+            builder.identity(identity.0.clone());
+        }
+        let tls_connector = builder.build()?;
+        let addr = (host, port);
+        let tls = match timeout {
+            None => {
+                let tcp = TcpStream::connect(addr)?;
+                match tls_connector.connect(host, tcp) {
+                    Ok(res) => res,
+                    Err(e) => {
+                        fail!((ErrorKind::IoError, "SSL Handshake error", e.to_string()));
+                    }
+                }
+            }
+            Some(timeout) => {
+                let mut tcp = None;
+                let mut last_error = None;
+                for addr in addr.to_socket_addrs()? {
+                    match TcpStream::connect_timeout(&addr, timeout) {
+                        Ok(l) => {
+                            tcp = Some(l);
+                            break;
+                        }
+                        Err(e) => {
+                            last_error = Some(e);
+                        }
+                    };
+                }
+                match (tcp, last_error) {
+                    (Some(tcp), _) => match tls_connector.connect(host, tcp) {
+                        Ok(res) => res,
+                        Err(e) => {
+                            fail!((ErrorKind::IoError, "SSL Handshake error", e.to_string()));
+                        }
+                    },
+                    (None, Some(e)) => {
+                        fail!(e);
+                    }
+                    (None, None) => {
+                        fail!((
+                            ErrorKind::InvalidClientConfig,
+                            "could not resolve to any addresses"
+                        ));
+                    }
+                }
+            }
+        };
+        Ok(ActualConnection::TcpTls(Box::new(TcpTlsConnection {
+            reader: tls,
+            open: true,
+        })))
+    }
+
     pub fn send_bytes(&mut self, bytes: &[u8]) -> RedisResult<Value> {
         match *self {
             ActualConnection::Tcp(ref mut connection) => {
@@ -548,6 +683,18 @@ impl ActualConnection {
         Ok(())
     }
 
+    pub fn read_timeout(&self) -> RedisResult<Option<Duration>> {
+        Ok(match *self {
+            ActualConnection::Tcp(TcpConnection { ref reader, .. }) => reader.read_timeout()?,
+            #[cfg(feature = "tls")]
+            ActualConnection::TcpTls(ref boxed_tls_connection) => {
+                boxed_tls_connection.reader.get_ref().read_timeout()?
+            }
+            #[cfg(unix)]
+            ActualConnection::Unix(UnixConnection { ref sock, .. }) => sock.read_timeout()?,
+        })
+    }
+
     pub fn is_open(&self) -> bool {
         match *self {
             ActualConnection::Tcp(TcpConnection { open, .. }) => open,
@@ -605,6 +752,25 @@ pub fn connect(
     setup_connection(con, &connection_info.redis)
 }
 
+/// Like [`connect`], but presents `identity` as the client certificate when
+/// the address uses TLS.
+#[cfg(feature = "tls")]
+pub(crate) fn connect_with_tls_identity(
+    connection_info: &ConnectionInfo,
+    timeout: Option<Duration>,
+    identity: &RedisIdentity,
+) -> RedisResult<Connection> {
+    let con = match connection_info.addr {
+        ConnectionAddr::TcpTls {
+            ref host,
+            port,
+            insecure,
+        } => ActualConnection::new_tls(host, port, insecure, Some(identity), timeout)?,
+        ref addr => ActualConnection::new(addr, timeout)?,
+    };
+    setup_connection(con, &connection_info.redis)
+}
+
 fn setup_connection(
     con: ActualConnection,
     connection_info: &RedisConnectionInfo,
@@ -613,6 +779,7 @@ fn setup_connection(
         con,
         parser: Parser::new(),
         db: connection_info.db,
+        protocol: ProtocolVersion::RESP2,
         pubsub: false,
     };
 
@@ -620,6 +787,11 @@ fn setup_connection(
         connect_auth(&mut rv, connection_info)?;
     }
 
+    if connection_info.protocol() == ProtocolVersion::RESP3 {
+        cmd("HELLO").arg(3).query::<Value>(&mut rv)?;
+        rv.protocol = ProtocolVersion::RESP3;
+    }
+
     if connection_info.db != 0 {
         match cmd("SELECT")
             .arg(connection_info.db)
@@ -733,6 +905,21 @@ impl Connection {
         self.con.set_read_timeout(dur)
     }
 
+    /// Returns the protocol version negotiated with the server.
+    pub fn get_protocol(&self) -> ProtocolVersion {
+        self.protocol
+    }
+
+    /// Switches the connection to the database `db` with `SELECT`.
+    ///
+    /// Unlike sending `SELECT` as a plain command, this keeps the database
+    /// reported by [`get_db`](ConnectionLike::get_db) up to date.
+    pub fn select_db(&mut self, db: i64) -> RedisResult<()> {
+        cmd("SELECT").arg(db).query::<()>(self)?;
+        self.db = db;
+        Ok(())
+    }
+
     /// Creates a [`PubSub`] instance for this connection.
     pub fn as_pubsub(&mut self) -> PubSub<'_> {
         // NOTE: The pubsub flag is intentionally not raised at this time since
@@ -981,6 +1168,33 @@ impl<'a> PubSub<'a> {
         cmd("PSUBSCRIBE").arg(pchannel).query(self.con)
     }
 
+    /// Subscribes to a new channel with a pattern, returning a guard that
+    /// unsubscribes from the pattern again when it is dropped.
+    ///
+    /// ```rust,no_run
+    /// # fn do_something() -> redis::RedisResult<()> {
+    /// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// # let mut con = client.get_connection().unwrap();
+    /// let mut pubsub = con.as_pubsub();
+    /// let mut subscription = pubsub.psubscribe_guarded("news.*")?;
+    /// for msg in subscription.messages().take(10) {
+    ///     let payload: String = msg.get_payload()?;
+    ///     println!("channel '{}': {}", msg.get_channel_name(), payload);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn psubscribe_guarded<T: ToRedisArgs>(
+        &mut self,
+        pchannel: T,
+    ) -> RedisResult<PatternSubscription<'_, 'a>> {
+        let pattern = pchannel.to_redis_args();
+        self.psubscribe(&pattern)?;
+        Ok(PatternSubscription {
+            pubsub: self,
+            pattern,
+        })
+    }
+
     /// Unsubscribes from a channel.
     pub fn unsubscribe<T: ToRedisArgs>(&mut self, channel: T) -> RedisResult<()> {
         cmd("UNSUBSCRIBE").arg(channel).query(self.con)
@@ -1007,6 +1221,24 @@ impl<'a> PubSub<'a> {
         }
     }
 
+    /// Fetches the next message from the pubsub connection, waiting at most
+    /// `timeout` for it to arrive.
+    ///
+    /// Returns `None` if no message arrived in time. The read timeout set with
+    /// `set_read_timeout` is restored afterwards. It is an error to pass the
+    /// zero `Duration` to this method.
+    pub fn get_message_timeout(&mut self, timeout: Duration) -> RedisResult<Option<Msg>> {
+        let previous = self.con.con.read_timeout()?;
+        self.con.set_read_timeout(Some(timeout))?;
+        let result = self.get_message();
+        self.con.set_read_timeout(previous)?;
+        match result {
+            Ok(msg) => Ok(Some(msg)),
+            Err(err) if err.is_timeout() => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Sets the read timeout for the connection.
     ///
     /// If the provided value is `None`, then `get_message` call will
@@ -1023,6 +1255,34 @@ impl<'a> Drop for PubSub<'a> {
     }
 }
 
+/// A pattern subscription created by [`PubSub::psubscribe_guarded`].
+///
+/// The pattern is unsubscribed from when the guard is dropped.
+pub struct PatternSubscription<'p, 'a> {
+    pubsub: &'p mut PubSub<'a>,
+    pattern: Vec<Vec<u8>>,
+}
+
+impl<'p, 'a> PatternSubscription<'p, 'a> {
+    /// Returns an iterator over the messages received by the underlying
+    /// pubsub connection.  The iterator ends on the first error, such as a
+    /// read timeout.
+    pub fn messages(&mut self) -> impl Iterator<Item = Msg> + '_ {
+        let con = &mut *self.pubsub.con;
+        std::iter::from_fn(move || loop {
+            if let Some(msg) = Msg::from_value(&con.recv_response().ok()?) {
+                return Some(msg);
+            }
+        })
+    }
+}
+
+impl<'p, 'a> Drop for PatternSubscription<'p, 'a> {
+    fn drop(&mut self) {
+        let _ = self.pubsub.punsubscribe(&self.pattern);
+    }
+}
+
 /// This holds the data that comes from listening to a pubsub
 /// connection.  It only contains actual message data.
 impl Msg {
@@ -1213,6 +1473,7 @@ mod tests {
                         db: 2,
                         username: Some("%johndoe%".to_string()),
                         password: Some("#@<>$".to_string()),
+                        use_resp3: false,
                     },
                 },
             ),
@@ -1235,6 +1496,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_connection_info_from_env() {
+        std::env::set_var("FROM_ENV_TEST_HOST", "redis.example.com");
+        std::env::set_var("FROM_ENV_TEST_PORT", "6380");
+        std::env::set_var("FROM_ENV_TEST_DB", "2");
+        std::env::set_var("FROM_ENV_TEST_PASSWORD", "secret");
+
+        let info = ConnectionInfo::from_env("FROM_ENV_TEST").unwrap();
+        assert_eq!(
+            info.addr,
+            ConnectionAddr::Tcp("redis.example.com".to_string(), 6380)
+        );
+        assert_eq!(info.redis.db, 2);
+        assert_eq!(info.redis.username, None);
+        assert_eq!(info.redis.password, Some("secret".to_string()));
+
+        let info = ConnectionInfo::from_env("FROM_ENV_UNSET").unwrap();
+        assert_eq!(
+            info.addr,
+            ConnectionAddr::Tcp("localhost".to_string(), 6379)
+        );
+        assert_eq!(info.redis.db, 0);
+        assert_eq!(info.redis.password, None);
+
+        std::env::set_var("FROM_ENV_INVALID_PORT", "redis");
+        let err = ConnectionInfo::from_env("FROM_ENV_INVALID").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidClientConfig);
+    }
+
     #[test]
     fn test_url_to_tcp_connection_info_failed() {
         let cases = vec![
@@ -1281,6 +1571,7 @@ mod tests {
                         db: 0,
                         username: None,
                         password: None,
+                        use_resp3: false,
                     },
                 },
             ),
@@ -1292,6 +1583,7 @@ mod tests {
                         db: 1,
                         username: None,
                         password: None,
+                        use_resp3: false,
                     },
                 },
             ),
@@ -1306,6 +1598,7 @@ mod tests {
                         db: 2,
                         username: Some("%johndoe%".to_string()),
                         password: Some("#@<>$".to_string()),
+                        use_resp3: false,
                     },
                 },
             ),
@@ -1320,6 +1613,7 @@ mod tests {
                         db: 2,
                         username: Some("%johndoe%".to_string()),
                         password: Some("&?= *+".to_string()),
+                        use_resp3: false,
                     },
                 },
             ),
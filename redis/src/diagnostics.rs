@@ -0,0 +1,120 @@
+//! Helpers to inspect how keys are stored, as building blocks for diagnostic
+//! tools.
+//!
+//! # Example
+//! ```rust,no_run
+//! # fn do_something() -> redis::RedisResult<()> {
+//! let client = redis::Client::open("redis://127.0.0.1/")?;
+//! let mut con = client.get_connection()?;
+//!
+//! println!("{}", redis::diagnostics::encoding_table(&mut con, &["counter", "queue"])?);
+//! // KEY      TYPE    ENCODING   MEMORY
+//! // counter  string  int            56
+//! // queue    list    quicklist     213
+//! # Ok(()) }
+//! ```
+use std::collections::HashMap;
+
+use crate::cmd::cmd;
+use crate::commands::Commands;
+use crate::connection::ConnectionLike;
+use crate::pipeline::Pipeline;
+use crate::types::{Encoding, RedisResult, ToRedisArgs};
+
+const HEADER: [&str; 4] = ["KEY", "TYPE", "ENCODING", "MEMORY"];
+
+/// Returns a table of the type, encoding and memory usage in bytes of each
+/// key, one line per key in the given order, preceded by a header line.
+///
+/// The information is fetched with `TYPE`, `OBJECT ENCODING` and
+/// `MEMORY USAGE` (Redis 4.0+) in a single pipeline. Keys that don't exist
+/// are listed with the type `none`. The columns are separated by two spaces
+/// and padded to the widest value, the memory usage being aligned right.
+pub fn encoding_table<C: ConnectionLike, K: ToRedisArgs>(
+    con: &mut C,
+    keys: &[K],
+) -> RedisResult<String> {
+    let mut pipe = Pipeline::new();
+    for key in keys {
+        pipe.cmd("TYPE")
+            .arg(key)
+            .object_encoding(key)
+            .cmd("MEMORY")
+            .arg("USAGE")
+            .arg(key);
+    }
+    let replies: Vec<(String, Option<Encoding>, Option<u64>)> = pipe.query(con)?;
+
+    let mut rows = vec![HEADER.map(str::to_string)];
+    for (key, (key_type, encoding, memory)) in keys.iter().zip(replies) {
+        let key = key
+            .to_redis_args()
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect::<Vec<_>>()
+            .join(" ");
+        rows.push([
+            key,
+            key_type,
+            encoding.map_or_else(|| "-".to_string(), |e| e.to_string()),
+            memory.map_or_else(|| "-".to_string(), |m| m.to_string()),
+        ]);
+    }
+
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in &rows {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {:>w3$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        table.push_str(&line);
+        table.push('\n');
+    }
+    Ok(table)
+}
+
+/// Returns the number of keys of each non-empty database, by database index.
+///
+/// The number of databases is read with `CONFIG GET databases`, so `CONFIG`
+/// must be allowed for the connection's user, which managed services often
+/// forbid. Each database is then selected in turn to send `DBSIZE`.
+///
+/// The database reported by [`ConnectionLike::get_db`] is selected again
+/// afterwards, even if a command fails. That is the one of
+/// [`RedisConnectionInfo::db`](crate::RedisConnectionInfo::db) or of the last
+/// [`Connection::select_db`](crate::Connection::select_db) call: a database
+/// selected by sending `SELECT` directly isn't restored. This is not supported
+/// by cluster connections, which only have database 0.
+pub fn dbsize_for_all_databases<C: ConnectionLike>(con: &mut C) -> RedisResult<HashMap<i64, u64>> {
+    let config: HashMap<String, i64> = cmd("CONFIG").arg("GET").arg("databases").query(con)?;
+    let databases = config.get("databases").copied().unwrap_or(0);
+
+    let original_db = con.get_db();
+    let sizes: RedisResult<HashMap<i64, u64>> =
+        (0..databases).try_fold(HashMap::new(), |mut sizes, db| {
+            cmd("SELECT").arg(db).query::<()>(con)?;
+            let size: u64 = con.dbsize()?;
+            if size > 0 {
+                sizes.insert(db, size);
+            }
+            Ok(sizes)
+        });
+    let restored = cmd("SELECT").arg(original_db).query::<()>(con);
+    let sizes = sizes?;
+    restored?;
+    Ok(sizes)
+}
@@ -149,6 +149,7 @@ impl Default for RadiusOrder {
 pub struct RadiusOptions {
     with_coord: bool,
     with_dist: bool,
+    with_hash: bool,
     count: Option<usize>,
     order: RadiusOrder,
     store: Option<Vec<Vec<u8>>>,
@@ -176,6 +177,12 @@ impl RadiusOptions {
         self
     }
 
+    /// Return the raw geohash-encoded sorted set score of the matching items.
+    pub fn with_hash(mut self) -> Self {
+        self.with_hash = true;
+        self
+    }
+
     /// Sort the returned items
     pub fn order(mut self, o: RadiusOrder) -> Self {
         self.order = o;
@@ -211,6 +218,10 @@ impl ToRedisArgs for RadiusOptions {
             out.write_arg(b"WITHDIST");
         }
 
+        if self.with_hash {
+            out.write_arg(b"WITHHASH");
+        }
+
         if let Some(n) = self.count {
             out.write_arg(b"COUNT");
             out.write_arg_fmt(n);
@@ -253,6 +264,8 @@ pub struct RadiusSearchResult {
     pub coord: Option<Coord<f64>>,
     /// The distance if available.
     pub dist: Option<f64>,
+    /// The geohash-encoded score if available.
+    pub hash: Option<u64>,
 }
 
 impl FromRedisValue for RadiusSearchResult {
@@ -263,6 +276,7 @@ impl FromRedisValue for RadiusSearchResult {
                 name,
                 coord: None,
                 dist: None,
+                hash: None,
             });
         }
 
@@ -289,11 +303,22 @@ impl RadiusSearchResult {
 
         let mut next = iter.next();
 
-        // Next element, if present, will be the distance.
-        let dist = match next.map(FromRedisValue::from_redis_value) {
-            Some(Ok(c)) => {
+        // Next element, if present, will be the distance, sent as a string.
+        let dist = match next {
+            Some(Value::Data(_)) => {
+                let dist = FromRedisValue::from_redis_value(next?).ok();
                 next = iter.next();
-                Some(c)
+                dist
+            }
+            _ => None,
+        };
+
+        // Then, if present, the hash, sent as an integer.
+        let hash = match next {
+            Some(Value::Int(_)) => {
+                let hash = FromRedisValue::from_redis_value(next?).ok();
+                next = iter.next();
+                hash
             }
             _ => None,
         };
@@ -305,7 +330,12 @@ impl RadiusSearchResult {
             _ => None,
         };
 
-        Some(RadiusSearchResult { name, coord, dist })
+        Some(RadiusSearchResult {
+            name,
+            coord,
+            dist,
+            hash,
+        })
     }
 }
 
@@ -342,6 +372,13 @@ mod tests {
 
         assert_args!(opts().with_coord().with_dist(), "WITHCOORD", "WITHDIST");
 
+        assert_args!(
+            opts().with_coord().with_dist().with_hash(),
+            "WITHCOORD",
+            "WITHDIST",
+            "WITHHASH"
+        );
+
         assert_args!(opts().limit(50), "COUNT", "50");
 
         assert_args!(opts().limit(50).store("x"), "COUNT", "50", "STORE", "x");
@@ -59,8 +59,11 @@
 //! * `r2d2`: enables r2d2 connection pool support (optional)
 //! * `ahash`: enables ahash map/set support & uses ahash internally (+7-10% performance) (optional)
 //! * `cluster`: enables redis cluster support (optional)
+//! * `cluster-diagnostics`: logs the encoding of a sample of the keys used by cluster connections (optional)
 //! * `tokio-comp`: enables support for tokio (optional)
 //! * `connection-manager`: enables support for automatic reconnection (optional)
+//! * `test-support`: enables commands only meant for testing, such as `DEBUG` subcommands (optional)
+//! * `tracing`: wraps command and pipeline dispatch in `tracing` spans (optional)
 //!
 //! ## Connection Parameters
 //!
@@ -361,18 +364,31 @@ assert_eq!(result, Ok(("foo".to_string(), b"bar".to_vec())));
 // public api
 pub use crate::client::Client;
 pub use crate::cmd::{cmd, pack_command, pipe, Arg, Cmd, Iter};
-pub use crate::commands::{Commands, ControlFlow, Direction, LposOptions, PubSubCommands};
+pub use crate::commands::{
+    ClientPauseMode, ClientType, CommandListFilter, Commands, ControlFlow, Direction, LposOptions,
+    MigrateOptions, PopDirection, PubSubCommands, RangeMode, RestorePolicy, ScanOptions, SortOrder,
+    ZRangeOptions, ZaddOptions,
+};
 pub use crate::connection::{
     parse_redis_url, transaction, Connection, ConnectionAddr, ConnectionInfo, ConnectionLike,
-    IntoConnectionInfo, Msg, PubSub, RedisConnectionInfo,
+    IntoConnectionInfo, Msg, PatternSubscription, ProtocolVersion, PubSub, RedisConnectionInfo,
 };
 pub use crate::parser::{parse_redis_value, Parser};
 pub use crate::pipeline::Pipeline;
+pub use crate::pool::RedisPool;
+
+#[cfg(feature = "tls")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+pub use crate::connection::RedisIdentity;
 
 #[cfg(feature = "script")]
 #[cfg_attr(docsrs, doc(cfg(feature = "script")))]
 pub use crate::script::{Script, ScriptInvocation};
 
+#[cfg(feature = "r2d2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "r2d2")))]
+pub use crate::r2d2::RedisConnectionManager;
+
 // preserve grouping and order
 #[rustfmt::skip]
 pub use crate::types::{
@@ -386,9 +402,26 @@ pub use crate::types::{
     FromRedisValue,
 
     // utility types
+    ClientInfo,
+    ClusterNode,
+    ClusterShard,
+    ClusterSlot,
+    CommandInfo,
+    Encoding,
+    EncodingOrType,
+    EngineStats,
+    FunctionStats,
     InfoDict,
+    KeyType,
     NumericBehavior,
+    RunningScript,
+    SentinelInfo,
+    SentinelMasterInfo,
+    SentinelSlaveInfo,
+    ServerInfo,
+    ServerRole,
     Expiry,
+    ZMPopReply,
 
     // error and result types
     RedisError,
@@ -408,6 +441,7 @@ pub use crate::{
 
 mod macros;
 mod pipeline;
+mod pool;
 
 #[cfg(feature = "acl")]
 #[cfg_attr(docsrs, doc(cfg(feature = "acl")))]
@@ -451,6 +485,10 @@ pub mod streams;
 #[cfg(feature = "cluster-async")]
 pub mod cluster_async;
 
+pub mod diagnostics;
+
+pub mod testing;
+
 mod client;
 mod cmd;
 mod commands;
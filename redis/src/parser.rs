@@ -55,6 +55,29 @@ where
 
 const MAX_RECURSE_DEPTH: usize = 100;
 
+fn parse_error(line: &str) -> RedisError {
+    let desc = "An error was signalled by the server";
+    let mut pieces = line.splitn(2, ' ');
+    let kind = match pieces.next().unwrap() {
+        "ERR" => ErrorKind::ResponseError,
+        "EXECABORT" => ErrorKind::ExecAbortError,
+        "LOADING" => ErrorKind::BusyLoadingError,
+        "NOSCRIPT" => ErrorKind::NoScriptError,
+        "MOVED" => ErrorKind::Moved,
+        "ASK" => ErrorKind::Ask,
+        "TRYAGAIN" => ErrorKind::TryAgain,
+        "CLUSTERDOWN" => ErrorKind::ClusterDown,
+        "CROSSSLOT" => ErrorKind::CrossSlot,
+        "MASTERDOWN" => ErrorKind::MasterDown,
+        "READONLY" => ErrorKind::ReadOnly,
+        code => return make_extension_error(code, pieces.next()),
+    };
+    match pieces.next() {
+        Some(detail) => RedisError::from((kind, desc, detail.to_string())),
+        None => RedisError::from((kind, desc)),
+    }
+}
+
 fn value<'a, I>(
     count: Option<usize>,
 ) -> impl combine::Parser<I, Output = RedisResult<Value>, PartialState = AnySendSyncPartialState>
@@ -67,7 +90,7 @@ where
     opaque!(any_send_sync_partial_state(
         any()
             .then_partial(move |&mut b| {
-                if b == b'*' && count > MAX_RECURSE_DEPTH {
+                if matches!(b, b'*' | b'%' | b'~' | b'>') && count > MAX_RECURSE_DEPTH {
                     combine::unexpected_any("Maximum recursion depth exceeded").left()
                 } else {
                     combine::value(b).right()
@@ -128,37 +151,90 @@ where
                     })
                 };
 
-                let error = || {
-                    line().map(|line: &str| {
-                        let desc = "An error was signalled by the server";
-                        let mut pieces = line.splitn(2, ' ');
-                        let kind = match pieces.next().unwrap() {
-                            "ERR" => ErrorKind::ResponseError,
-                            "EXECABORT" => ErrorKind::ExecAbortError,
-                            "LOADING" => ErrorKind::BusyLoadingError,
-                            "NOSCRIPT" => ErrorKind::NoScriptError,
-                            "MOVED" => ErrorKind::Moved,
-                            "ASK" => ErrorKind::Ask,
-                            "TRYAGAIN" => ErrorKind::TryAgain,
-                            "CLUSTERDOWN" => ErrorKind::ClusterDown,
-                            "CROSSSLOT" => ErrorKind::CrossSlot,
-                            "MASTERDOWN" => ErrorKind::MasterDown,
-                            "READONLY" => ErrorKind::ReadOnly,
-                            code => return make_extension_error(code, pieces.next()),
-                        };
-                        match pieces.next() {
-                            Some(detail) => RedisError::from((kind, desc, detail.to_string())),
-                            None => RedisError::from((kind, desc)),
+                let map = || {
+                    int().then_partial(move |&mut length| {
+                        if length < 0 {
+                            combine::value(Value::Nil).map(Ok).left()
+                        } else {
+                            let length = length as usize * 2;
+                            combine::count_min_max(length, length, value(Some(count + 1)))
+                                .map(|result: ResultExtend<Vec<_>, _>| {
+                                    result.0.map(|items| {
+                                        let mut items = items.into_iter();
+                                        let mut pairs = Vec::with_capacity(items.len() / 2);
+                                        while let (Some(k), Some(v)) = (items.next(), items.next())
+                                        {
+                                            pairs.push((k, v));
+                                        }
+                                        Value::Map(pairs)
+                                    })
+                                })
+                                .right()
+                        }
+                    })
+                };
+
+                let set = || {
+                    int().then_partial(move |&mut length| {
+                        if length < 0 {
+                            combine::value(Value::Nil).map(Ok).left()
+                        } else {
+                            let length = length as usize;
+                            combine::count_min_max(length, length, value(Some(count + 1)))
+                                .map(|result: ResultExtend<_, _>| result.0.map(Value::Set))
+                                .right()
+                        }
+                    })
+                };
+
+                let boolean = || {
+                    line().and_then(|line| match line {
+                        "t" => Ok(Value::Int(1)),
+                        "f" => Ok(Value::Int(0)),
+                        _ => Err(StreamErrorFor::<I>::message_static_message(
+                            "Expected boolean, got garbage",
+                        )),
+                    })
+                };
+
+                let verbatim = || {
+                    data().and_then(|value| match value {
+                        // Drop the three letter format and the colon in front of the text.
+                        Value::Data(mut bytes) if bytes.len() >= 4 && bytes[3] == b':' => {
+                            bytes.drain(..4);
+                            Ok(Value::Data(bytes))
                         }
+                        Value::Nil => Ok(Value::Nil),
+                        _ => Err(StreamErrorFor::<I>::message_static_message(
+                            "Expected verbatim string, got garbage",
+                        )),
                     })
                 };
 
+                let error = || line().map(parse_error);
+
                 combine::dispatch!(b;
                     b'+' => status().map(Ok),
                     b':' => int().map(|i| Ok(Value::Int(i))),
                     b'$' => data().map(Ok),
                     b'*' => bulk(),
                     b'-' => error().map(Err),
+                    // RESP3 types
+                    b'_' => line().map(|_| Ok(Value::Nil)),
+                    b',' | b'(' => line().map(|line| Ok(Value::Data(line.as_bytes().to_vec()))),
+                    b'#' => boolean().map(Ok),
+                    b'=' => verbatim().map(Ok),
+                    b'%' => map(),
+                    b'~' => set(),
+                    b'>' => bulk(),
+                    b'!' => data().and_then(|value| match value {
+                        Value::Data(bytes) => str::from_utf8(&bytes)
+                            .map(|line| Err(parse_error(line)))
+                            .map_err(StreamErrorFor::<I>::other),
+                        _ => Err(StreamErrorFor::<I>::message_static_message(
+                            "Expected blob error, got garbage",
+                        )),
+                    }),
                     b => combine::unexpected_any(combine::error::Token(b))
                 )
             })
@@ -351,6 +427,48 @@ mod tests {
         assert_eq!(codec.decode_eof(&mut bytes), Ok(None));
     }
 
+    #[test]
+    fn parse_resp3_types() {
+        assert_eq!(parse_redis_value(b"_\r\n"), Ok(Value::Nil));
+        assert_eq!(parse_redis_value(b"#t\r\n"), Ok(Value::Int(1)));
+        assert_eq!(parse_redis_value(b"#f\r\n"), Ok(Value::Int(0)));
+        assert_eq!(
+            parse_redis_value(b",1.5\r\n"),
+            Ok(Value::Data(b"1.5".to_vec()))
+        );
+        assert_eq!(
+            parse_redis_value(b"=15\r\ntxt:Some string\r\n"),
+            Ok(Value::Data(b"Some string".to_vec()))
+        );
+        assert_eq!(
+            parse_redis_value(b"%2\r\n+first\r\n:1\r\n+second\r\n:2\r\n"),
+            Ok(Value::Map(vec![
+                (Value::Status("first".into()), Value::Int(1)),
+                (Value::Status("second".into()), Value::Int(2)),
+            ]))
+        );
+        assert_eq!(
+            parse_redis_value(b"~2\r\n+orange\r\n+apple\r\n"),
+            Ok(Value::Set(vec![
+                Value::Status("orange".into()),
+                Value::Status("apple".into()),
+            ]))
+        );
+        assert_eq!(
+            parse_redis_value(b">2\r\n+message\r\n+hello\r\n"),
+            Ok(Value::Bulk(vec![
+                Value::Status("message".into()),
+                Value::Status("hello".into()),
+            ]))
+        );
+        assert_eq!(
+            parse_redis_value(b"!21\r\nSYNTAX invalid syntax\r\n")
+                .unwrap_err()
+                .code(),
+            Some("SYNTAX")
+        );
+    }
+
     #[test]
     fn test_max_recursion_depth() {
         let bytes = b"*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n*1\r\n";
@@ -5,6 +5,8 @@ use crate::connection::ConnectionLike;
 use crate::types::{
     from_redis_value, ErrorKind, FromRedisValue, HashSet, RedisResult, ToRedisArgs, Value,
 };
+#[cfg(feature = "tokio-comp")]
+use std::{io, time::Duration};
 
 /// Represents a redis command pipeline.
 #[derive(Clone)]
@@ -12,6 +14,8 @@ pub struct Pipeline {
     commands: Vec<Cmd>,
     transaction_mode: bool,
     ignored_commands: HashSet<usize>,
+    // `WATCH` command sent before `MULTI` in atomic mode.
+    watch: Option<Cmd>,
 }
 
 /// A pipeline allows you to send multiple commands in one go to the
@@ -48,9 +52,39 @@ impl Pipeline {
             commands: Vec::with_capacity(capacity),
             transaction_mode: false,
             ignored_commands: HashSet::new(),
+            watch: None,
         }
     }
 
+    /// Creates an empty pipeline in atomic mode that also watches `keys`:
+    /// `WATCH` is sent right before the `MULTI`/`EXEC` block, in the same
+    /// round trip.
+    ///
+    /// If any of the keys is modified by another client before `EXEC`, the
+    /// transaction is not executed and the query fails with
+    /// [`ErrorKind::TransactionAborted`](crate::ErrorKind::TransactionAborted)
+    /// rather than returning nil.
+    ///
+    /// As nothing can be read in between, this only guards against changes
+    /// made while the pipeline is processed. To read values before writing
+    /// them back, use [`transaction`](crate::transaction) instead.
+    ///
+    /// ```rust,no_run
+    /// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+    /// # let mut con = client.get_connection().unwrap();
+    /// let (k1,) : (i32,) = redis::Pipeline::atomic_with_watch(&["key_1"])
+    ///     .cmd("INCR").arg("key_1")
+    ///     .query(&mut con).unwrap();
+    /// ```
+    pub fn atomic_with_watch<K: ToRedisArgs>(keys: &[K]) -> Pipeline {
+        let mut watch = cmd("WATCH");
+        watch.arg(keys);
+        let mut pipeline = Pipeline::new();
+        pipeline.atomic();
+        pipeline.watch = Some(watch);
+        pipeline
+    }
+
     /// This enables atomic mode.  In atomic mode the whole pipeline is
     /// enclosed in `MULTI`/`EXEC`.  From the user's point of view nothing
     /// changes however.  This is easier than using `MULTI`/`EXEC` yourself
@@ -70,32 +104,66 @@ impl Pipeline {
         self
     }
 
+    /// Clear a pipeline object's internal data structure.
+    ///
+    /// This allows reusing a pipeline object as a clear object while performing a minimal
+    /// amount of memory released/reallocated. The keys watched by a pipeline created with
+    /// [`atomic_with_watch`](Pipeline::atomic_with_watch) are dropped as well.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.ignored_commands.clear();
+        self.watch = None;
+    }
+
     /// Returns the encoded pipeline commands.
     pub fn get_packed_pipeline(&self) -> Vec<u8> {
-        encode_pipeline(&self.commands, self.transaction_mode)
+        encode_pipeline(self.watch.as_ref(), &self.commands, self.transaction_mode)
     }
 
     #[cfg(feature = "aio")]
     pub(crate) fn write_packed_pipeline(&self, out: &mut Vec<u8>) {
-        write_pipeline(out, &self.commands, self.transaction_mode)
+        write_pipeline(
+            out,
+            self.watch.as_ref(),
+            &self.commands,
+            self.transaction_mode,
+        )
     }
 
     fn execute_pipelined(&self, con: &mut dyn ConnectionLike) -> RedisResult<Value> {
         Ok(self.make_pipeline_results(con.req_packed_commands(
-            &encode_pipeline(&self.commands, false),
+            &encode_pipeline(None, &self.commands, false),
             0,
             self.commands.len(),
         )?))
     }
 
+    // The number of replies before the one to `EXEC`.
+    fn transaction_offset(&self) -> usize {
+        self.commands.len() + 1 + usize::from(self.watch.is_some())
+    }
+
+    // `EXEC` replies nil when a watched key was modified, which is an error
+    // for pipelines created with `atomic_with_watch`.
+    fn aborted_transaction(&self) -> RedisResult<Value> {
+        if self.watch.is_some() {
+            fail!((
+                ErrorKind::TransactionAborted,
+                "Transaction aborted because a watched key was modified"
+            ));
+        }
+        Ok(Value::Nil)
+    }
+
     fn execute_transaction(&self, con: &mut dyn ConnectionLike) -> RedisResult<Value> {
         let mut resp = con.req_packed_commands(
-            &encode_pipeline(&self.commands, true),
-            self.commands.len() + 1,
+            &encode_pipeline(self.watch.as_ref(), &self.commands, true),
+            self.transaction_offset(),
             1,
         )?;
         match resp.pop() {
-            Some(Value::Nil) => Ok(Value::Nil),
+            Some(Value::Nil) => self.aborted_transaction(),
             Some(Value::Bulk(items)) => Ok(self.make_pipeline_results(items)),
             _ => fail!((
                 ErrorKind::ResponseError,
@@ -123,6 +191,8 @@ impl Pipeline {
     ///       it is necessary to call the `clear()` before inserting new commands.
     #[inline]
     pub fn query<T: FromRedisValue>(&self, con: &mut dyn ConnectionLike) -> RedisResult<T> {
+        #[cfg(feature = "tracing")]
+        let _span = self.span().entered();
         if !con.supports_pipelining() {
             fail!((
                 ErrorKind::ResponseError,
@@ -145,9 +215,10 @@ impl Pipeline {
     where
         C: crate::aio::ConnectionLike,
     {
-        let value = con
-            .req_packed_commands(self, 0, self.commands.len())
-            .await?;
+        let request = con.req_packed_commands(self, 0, self.commands.len());
+        #[cfg(feature = "tracing")]
+        let request = tracing::Instrument::instrument(request, self.span());
+        let value = request.await?;
         Ok(self.make_pipeline_results(value))
     }
 
@@ -156,11 +227,12 @@ impl Pipeline {
     where
         C: crate::aio::ConnectionLike,
     {
-        let mut resp = con
-            .req_packed_commands(self, self.commands.len() + 1, 1)
-            .await?;
+        let request = con.req_packed_commands(self, self.transaction_offset(), 1);
+        #[cfg(feature = "tracing")]
+        let request = tracing::Instrument::instrument(request, self.span());
+        let mut resp = request.await?;
         match resp.pop() {
-            Some(Value::Nil) => Ok(Value::Nil),
+            Some(Value::Nil) => self.aborted_transaction(),
             Some(Value::Bulk(items)) => Ok(self.make_pipeline_results(items)),
             _ => Err((
                 ErrorKind::ResponseError,
@@ -170,6 +242,17 @@ impl Pipeline {
         }
     }
 
+    /// The span a pipeline is sent in, recording the number of commands and
+    /// whether it is a transaction. The commands themselves aren't recorded.
+    #[cfg(feature = "tracing")]
+    fn span(&self) -> tracing::Span {
+        tracing::info_span!(
+            "redis.pipeline",
+            cmds = self.commands.len(),
+            transaction = self.transaction_mode
+        )
+    }
+
     /// Async version of `query`.
     #[inline]
     #[cfg(feature = "aio")]
@@ -187,6 +270,32 @@ impl Pipeline {
         from_redis_value(&v)
     }
 
+    /// Like [`query_async`](Pipeline::query_async), but fails with a timeout
+    /// error (see [`RedisError::is_timeout`](crate::RedisError::is_timeout))
+    /// if the pipeline doesn't complete within `timeout`.
+    ///
+    /// On timeout, the state of the connection is unknown so it is closed:
+    /// an [`aio::Connection`](crate::aio::Connection) fails all the following
+    /// requests, and a [`ConnectionManager`](crate::aio::ConnectionManager)
+    /// reconnects. See [`abandon_request`](crate::aio::ConnectionLike::abandon_request).
+    #[cfg(feature = "tokio-comp")]
+    pub async fn query_async_with_timeout<C, T: FromRedisValue>(
+        &self,
+        con: &mut C,
+        timeout: Duration,
+    ) -> RedisResult<T>
+    where
+        C: crate::aio::ConnectionLike,
+    {
+        match tokio::time::timeout(timeout, self.query_async(con)).await {
+            Ok(result) => result,
+            Err(_) => {
+                con.abandon_request();
+                Err(io::Error::from(io::ErrorKind::TimedOut).into())
+            }
+        }
+    }
+
     /// This is a shortcut to `query()` that does not return a value and
     /// will fail the task if the query of the pipeline fails.
     ///
@@ -207,20 +316,24 @@ impl Pipeline {
     }
 }
 
-fn encode_pipeline(cmds: &[Cmd], atomic: bool) -> Vec<u8> {
+fn encode_pipeline(watch: Option<&Cmd>, cmds: &[Cmd], atomic: bool) -> Vec<u8> {
     let mut rv = vec![];
-    write_pipeline(&mut rv, cmds, atomic);
+    write_pipeline(&mut rv, watch, cmds, atomic);
     rv
 }
 
-fn write_pipeline(rv: &mut Vec<u8>, cmds: &[Cmd], atomic: bool) {
+fn write_pipeline(rv: &mut Vec<u8>, watch: Option<&Cmd>, cmds: &[Cmd], atomic: bool) {
     let cmds_len = cmds.iter().map(cmd_len).sum();
 
     if atomic {
         let multi = cmd("MULTI");
         let exec = cmd("EXEC");
-        rv.reserve(cmd_len(&multi) + cmd_len(&exec) + cmds_len);
+        let watch_len = watch.map(cmd_len).unwrap_or(0);
+        rv.reserve(watch_len + cmd_len(&multi) + cmd_len(&exec) + cmds_len);
 
+        if let Some(watch) = watch {
+            watch.write_packed_command_preallocated(rv);
+        }
         multi.write_packed_command_preallocated(rv);
         for cmd in cmds {
             cmd.write_packed_command_preallocated(rv);
@@ -285,16 +398,6 @@ macro_rules! implement_pipeline_commands {
                 self
             }
 
-            /// Clear a pipeline object's internal data structure.
-            ///
-            /// This allows reusing a pipeline object as a clear object while performing a minimal
-            /// amount of memory released/reallocated.
-            #[inline]
-            pub fn clear(&mut self) {
-                self.commands.clear();
-                self.ignored_commands.clear();
-            }
-
             #[inline]
             fn get_last_command(&mut self) -> &mut Cmd {
                 let idx = match self.commands.len() {
@@ -0,0 +1,41 @@
+use std::ops::DerefMut;
+
+use crate::connection::ConnectionLike;
+use crate::types::RedisResult;
+
+/// A pool of connections of type `C`, independent of the pool library.
+///
+/// Code that only needs to check out connections can be written against
+/// this trait and keep working when the pool implementation changes.
+/// With the `r2d2` feature it is implemented for `r2d2::Pool<Client>` and,
+/// together with the `cluster` feature, `r2d2::Pool<ClusterClient>`.
+///
+/// ```rust,no_run
+/// use redis::{Commands, Connection, RedisPool, RedisResult};
+///
+/// fn visits<P: RedisPool<Connection>>(pool: &P) -> RedisResult<u64> {
+///     let mut con = pool.get()?;
+///     let count = con.incr("visits", 1)?;
+///     pool.put(con);
+///     Ok(count)
+/// }
+/// ```
+pub trait RedisPool<C: ConnectionLike> {
+    /// A connection checked out from the pool.
+    type Pooled: DerefMut<Target = C>;
+
+    /// Checks out a connection, waiting for one to become available if
+    /// necessary.
+    ///
+    /// When no connection becomes available in time, the error is a timeout,
+    /// see [`RedisError::is_timeout`](crate::RedisError::is_timeout).
+    fn get(&self) -> RedisResult<Self::Pooled>;
+
+    /// Returns a connection to the pool.
+    ///
+    /// The default implementation drops the connection, which is enough for
+    /// pools that take connections back when they are dropped.
+    fn put(&self, conn: Self::Pooled) {
+        drop(conn);
+    }
+}
@@ -1,6 +1,48 @@
 use std::io;
 
-use crate::{ConnectionLike, RedisError};
+use crate::{Client, Cmd, Connection, ConnectionLike, RedisError, RedisPool, RedisResult};
+
+/// Opens the connections of an R2D2 pool to a single server like [`Client`]
+/// does, with options on how they are set up.
+///
+/// ```rust,no_run
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// use redis::RedisConnectionManager;
+///
+/// let manager = RedisConnectionManager::new(client).with_no_evict(true);
+/// let pool = r2d2::Pool::builder().max_size(5).build(manager).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RedisConnectionManager {
+    client: Client,
+    no_evict: bool,
+}
+
+impl RedisConnectionManager {
+    /// Creates a manager opening connections with `client`.
+    pub fn new(client: Client) -> RedisConnectionManager {
+        RedisConnectionManager {
+            client,
+            no_evict: false,
+        }
+    }
+
+    /// Sets whether `CLIENT NO-EVICT ON` is sent on new connections, so that
+    /// the server doesn't close pooled connections when client eviction
+    /// kicks in (Redis 7.0+, default is disabled).
+    pub fn with_no_evict(mut self, no_evict: bool) -> RedisConnectionManager {
+        self.no_evict = no_evict;
+        self
+    }
+
+    fn get_connection(&self) -> RedisResult<Connection> {
+        let mut con = self.client.get_connection()?;
+        if self.no_evict {
+            Cmd::client_no_evict(true).query::<()>(&mut con)?;
+        }
+        Ok(con)
+    }
+}
 
 /// Implementation of Redis connections for R2D2 connection pool
 ///
@@ -41,9 +83,57 @@ macro_rules! impl_manage_connection {
 }
 
 impl_manage_connection!(crate::Client, crate::Connection);
+impl_manage_connection!(RedisConnectionManager, crate::Connection);
 
 #[cfg(feature = "cluster")]
 impl_manage_connection!(
     crate::cluster::ClusterClient,
     crate::cluster::ClusterConnection
 );
+
+macro_rules! impl_redis_pool {
+    ($client:ty, $connection:ty) => {
+        impl RedisPool<$connection> for r2d2::Pool<$client> {
+            type Pooled = r2d2::PooledConnection<$client>;
+
+            fn get(&self) -> RedisResult<Self::Pooled> {
+                // r2d2 only fails to check out a connection when it times out.
+                r2d2::Pool::get(self)
+                    .map_err(|err| RedisError::from(io::Error::new(io::ErrorKind::TimedOut, err)))
+            }
+        }
+    };
+}
+
+impl_redis_pool!(crate::Client, crate::Connection);
+impl_redis_pool!(RedisConnectionManager, crate::Connection);
+
+#[cfg(feature = "cluster")]
+impl_redis_pool!(
+    crate::cluster::ClusterClient,
+    crate::cluster::ClusterConnection
+);
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{Client, RedisPool};
+
+    #[test]
+    fn test_get_timeout() {
+        // Nothing listens on port 1, so no connection can be checked out.
+        let client = Client::open("redis://127.0.0.1:1/").unwrap();
+        let pool = r2d2::Pool::builder()
+            .connection_timeout(Duration::from_millis(100))
+            .build_unchecked(client);
+
+        match RedisPool::get(&pool) {
+            Ok(_) => panic!("checked out a connection to a closed port"),
+            Err(err) => {
+                assert!(err.is_timeout());
+                assert!(err.to_string().contains("Connection refused"), "{}", err);
+            }
+        }
+    }
+}
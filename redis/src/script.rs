@@ -44,6 +44,14 @@ impl Script {
         &self.hash
     }
 
+    /// Returns the script's SHA1 hash in hexadecimal format.
+    ///
+    /// This is the same digest `SCRIPT LOAD` returns for the script, and an
+    /// alias of [`Script::get_hash`].
+    pub fn sha1(&self) -> &str {
+        &self.hash
+    }
+
     /// Creates a script invocation object with a key filled in.
     #[inline]
     pub fn key<T: ToRedisArgs>(&self, key: T) -> ScriptInvocation<'_> {
@@ -0,0 +1,108 @@
+//! Helpers for integration tests that run against a live redis server.
+//!
+//! # Example
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use redis::{Commands, Encoding};
+//!
+//! # fn do_something() -> redis::RedisResult<()> {
+//! let client = redis::Client::open("redis://127.0.0.1/")?;
+//! let mut con = client.get_connection()?;
+//!
+//! let _: () = con.set("key", "0123456789012345678901234567890123456789012345")?;
+//! redis::testing::wait_for_encoding(&mut con, "key", Encoding::Raw, Duration::from_secs(1))?;
+//! # Ok(()) }
+//! ```
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::commands::Commands;
+use crate::connection::ConnectionLike;
+use crate::types::{Encoding, ErrorKind, RedisError, RedisResult, ToRedisArgs};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+const MIN_BACKOFF: Duration = Duration::from_millis(1);
+const MAX_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Polls `OBJECT ENCODING` for `key` until the server reports `encoding`.
+///
+/// A key that doesn't exist yet is treated like a key with a different
+/// encoding, so this can be called before the key is written.
+///
+/// # Errors
+///
+/// Returns a timeout error (see [`RedisError::is_timeout`](crate::RedisError::is_timeout))
+/// if the key still doesn't have the expected encoding after `timeout`, and
+/// any error the server replies with in the meantime.
+pub fn wait_for_encoding<C: ConnectionLike, K: ToRedisArgs>(
+    con: &mut C,
+    key: K,
+    encoding: Encoding,
+    timeout: Duration,
+) -> RedisResult<()> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let current: Option<Encoding> = con.object_encoding(&key)?;
+        if current.as_ref() == Some(&encoding) {
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("encoding is still {current:?}, expected {encoding}"),
+            )
+            .into());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Polls `OBJECT ENCODING` for `key` until the server converts it from the
+/// `from` encoding to the `to` encoding, such as a list going from `Ziplist`
+/// to `Quicklist` once enough elements were inserted.
+///
+/// The delay between polls starts at 1 ms and doubles up to 100 ms.
+///
+/// # Errors
+///
+/// Returns a timeout error (see [`RedisError::is_timeout`](crate::RedisError::is_timeout))
+/// if the key is still encoded as `from` after `timeout`, an error of kind
+/// [`ErrorKind::ClientError`] if the key doesn't exist or has another encoding,
+/// and any error the server replies with in the meantime.
+pub fn await_encoding_change<C: ConnectionLike, K: ToRedisArgs>(
+    con: &mut C,
+    key: K,
+    from: Encoding,
+    to: Encoding,
+    timeout: Duration,
+) -> RedisResult<()> {
+    let deadline = Instant::now() + timeout;
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        let current: Option<Encoding> = con.object_encoding(&key)?;
+        match current {
+            Some(ref encoding) if *encoding == to => return Ok(()),
+            Some(ref encoding) if *encoding == from => {}
+            _ => {
+                return Err(RedisError::from((
+                    ErrorKind::ClientError,
+                    "Unexpected encoding",
+                    format!("encoding is {current:?}, expected {from} or {to}"),
+                )))
+            }
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("encoding is still {from}, expected {to}"),
+            )
+            .into());
+        }
+        thread::sleep(backoff.min(deadline - now));
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
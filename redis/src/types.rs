@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::convert::From;
 use std::default::Default;
@@ -6,7 +7,7 @@ use std::ffi::{CString, NulError};
 use std::fmt;
 use std::hash::{BuildHasher, Hash};
 use std::io;
-use std::str::{from_utf8, Utf8Error};
+use std::str::{from_utf8, FromStr, Utf8Error};
 use std::string::FromUtf8Error;
 
 #[cfg(feature = "ahash")]
@@ -14,6 +15,8 @@ pub(crate) use ahash::{AHashMap as HashMap, AHashSet as HashSet};
 #[cfg(not(feature = "ahash"))]
 pub(crate) use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
 
 macro_rules! invalid_type_error {
     ($v:expr, $det:expr) => {{
@@ -57,6 +60,157 @@ pub enum NumericBehavior {
     NumberIsFloat,
 }
 
+/// The internal representation of a value, as reported by
+/// [OBJECT ENCODING](https://redis.io/commands/object-encoding).
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// A string stored as a plain byte buffer.
+    Raw,
+    /// A string that can be represented as a 64 bit signed integer.
+    Int,
+    /// A short string embedded in the object header.
+    Embstr,
+    /// A compact encoding for small lists, hashes and sorted sets, replaced
+    /// by [`Listpack`](Encoding::Listpack) in Redis 7.0.
+    Ziplist,
+    /// A compact encoding for small hashes and sorted sets, reported instead
+    /// of [`Ziplist`](Encoding::Ziplist) since Redis 7.0. Redis 7.2 also uses
+    /// it for small sets of strings.
+    Listpack,
+    /// The pre Redis 3.2 encoding for large lists.
+    Linkedlist,
+    /// A linked list of ziplists, used for lists.
+    Quicklist,
+    /// A compact encoding for small sets containing only integers.
+    Intset,
+    /// The general encoding for sets and hashes.
+    Hashtable,
+    /// The general encoding for sorted sets.
+    Skiplist,
+    /// The encoding used by streams.
+    Stream,
+    /// A JSON document stored by the RedisJSON module, reported as `json` by
+    /// recent versions of the module.
+    ///
+    /// Older versions report JSON keys as `embstr` or `raw`, which can't be
+    /// told apart from strings and decode to [`Embstr`](Encoding::Embstr) and
+    /// [`Raw`](Encoding::Raw). Use [`type_of`](crate::Commands::type_of),
+    /// which reports `ReJSON-RL` for such keys, when this matters.
+    Json,
+    /// A value of a type defined by a module, reported as
+    /// `<module_name>-<encoding>`, for instance `ReJSON-RL`. Only the names of
+    /// the RedisJSON, RedisBloom and RedisTimeSeries types are recognized,
+    /// see [`MODULE_TYPE_PREFIXES`](Encoding::MODULE_TYPE_PREFIXES).
+    Module {
+        /// The name of the module type, such as `ReJSON`.
+        module_name: String,
+        /// The part of the name following the first dash, such as `RL`.
+        encoding: String,
+    },
+    /// An encoding this library doesn't know about.
+    Unknown(String),
+}
+
+impl Encoding {
+    /// The module type names parsed as [`Encoding::Module`]: RedisJSON
+    /// (`ReJSON`), RedisBloom (`MBbloom`, `CMSk`, `TopK`, `TDIS`) and
+    /// RedisTimeSeries (`TSDB`).
+    pub const MODULE_TYPE_PREFIXES: &'static [&'static str] =
+        &["ReJSON", "MBbloom", "CMSk", "TopK", "TDIS", "TSDB"];
+
+    /// Returns the encoding name the way the server reports it.
+    pub fn as_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            Encoding::Raw => "raw",
+            Encoding::Int => "int",
+            Encoding::Embstr => "embstr",
+            Encoding::Ziplist => "ziplist",
+            Encoding::Listpack => "listpack",
+            Encoding::Linkedlist => "linkedlist",
+            Encoding::Quicklist => "quicklist",
+            Encoding::Intset => "intset",
+            Encoding::Hashtable => "hashtable",
+            Encoding::Skiplist => "skiplist",
+            Encoding::Stream => "stream",
+            Encoding::Json => "json",
+            Encoding::Module {
+                module_name,
+                encoding,
+            } => return Cow::Owned(format!("{module_name}-{encoding}")),
+            Encoding::Unknown(encoding) => encoding,
+        })
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_str())
+    }
+}
+
+/// The type of the value stored at a key, as reported by
+/// [TYPE](https://redis.io/commands/type).
+///
+/// Keys that don't exist are decoded as `None` when reading an `Option<KeyType>`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum KeyType {
+    /// A string.
+    String,
+    /// A list.
+    List,
+    /// A set.
+    Set,
+    /// A sorted set.
+    ZSet,
+    /// A hash.
+    Hash,
+    /// A stream.
+    Stream,
+    /// A type this library doesn't know about, such as one added by a module.
+    Unknown(String),
+}
+
+impl KeyType {
+    /// Returns the type name the way the server reports it.
+    pub fn as_str(&self) -> &str {
+        match self {
+            KeyType::String => "string",
+            KeyType::List => "list",
+            KeyType::Set => "set",
+            KeyType::ZSet => "zset",
+            KeyType::Hash => "hash",
+            KeyType::Stream => "stream",
+            KeyType::Unknown(key_type) => key_type,
+        }
+    }
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ToRedisArgs for KeyType {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.as_str().as_bytes())
+    }
+}
+
+/// The encoding of a key, or its type when the encoding couldn't be read, as
+/// returned by [`object_encoding_or_type`](crate::Commands::object_encoding_or_type).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum EncodingOrType {
+    /// The encoding reported by `OBJECT ENCODING`.
+    Encoding(Encoding),
+    /// The type reported by `TYPE`, when `OBJECT ENCODING` failed.
+    Type(KeyType),
+}
+
 /// An enum of all error kinds.
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[non_exhaustive]
@@ -99,6 +253,8 @@ pub enum ErrorKind {
     ExtensionError,
     /// Attempt to write to a read-only server
     ReadOnly,
+    /// A transaction was not executed because a watched key was modified.
+    TransactionAborted,
 
     #[cfg(feature = "json")]
     /// Error Serializing a struct to JSON form
@@ -124,20 +280,39 @@ pub enum Value {
     Status(String),
     /// A status response which represents the string "OK".
     Okay,
+    /// A map response (RESP3 only), as a list of key/value pairs in the
+    /// order the server sent them.
+    Map(Vec<(Value, Value)>),
+    /// A set response (RESP3 only).
+    Set(Vec<Value>),
 }
 
-pub struct MapIter<'a>(std::slice::Iter<'a, Value>);
+pub struct MapIter<'a>(MapIterInner<'a>);
+
+enum MapIterInner<'a> {
+    // RESP2 maps are sent as a flat list of alternating keys and values.
+    Flat(std::slice::Iter<'a, Value>),
+    Pairs(std::slice::Iter<'a, (Value, Value)>),
+}
 
 impl<'a> Iterator for MapIter<'a> {
     type Item = (&'a Value, &'a Value);
 
     fn next(&mut self) -> Option<Self::Item> {
-        Some((self.0.next()?, self.0.next()?))
+        match self.0 {
+            MapIterInner::Flat(ref mut iter) => Some((iter.next()?, iter.next()?)),
+            MapIterInner::Pairs(ref mut iter) => iter.next().map(|(k, v)| (k, v)),
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (low, high) = self.0.size_hint();
-        (low / 2, high.map(|h| h / 2))
+        match self.0 {
+            MapIterInner::Flat(ref iter) => {
+                let (low, high) = iter.size_hint();
+                (low / 2, high.map(|h| h / 2))
+            }
+            MapIterInner::Pairs(ref iter) => iter.size_hint(),
+        }
     }
 }
 
@@ -177,10 +352,38 @@ impl Value {
         }
     }
 
+    /// Checks if the value is a nil response.
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Value::Nil)
+    }
+
+    /// Checks if the value is an `OK` status response.
+    pub fn is_okay(&self) -> bool {
+        matches!(self, Value::Okay)
+    }
+
+    /// Returns the items of a bulk response. Unlike `as_sequence`, this
+    /// does not accept sets or nil.
+    pub fn as_bulk(&self) -> Option<&[Value]> {
+        match self {
+            Value::Bulk(items) => Some(&items[..]),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an integer response. Integers sent as data are
+    /// not parsed, use `FromRedisValue` for that.
+    pub fn as_integer(&self) -> Option<i64> {
+        match *self {
+            Value::Int(val) => Some(val),
+            _ => None,
+        }
+    }
+
     /// Returns an `&[Value]` if `self` is compatible with a sequence type
     pub fn as_sequence(&self) -> Option<&[Value]> {
         match self {
-            Value::Bulk(items) => Some(&items[..]),
+            Value::Bulk(items) | Value::Set(items) => Some(&items[..]),
             Value::Nil => Some(&[]),
             _ => None,
         }
@@ -189,7 +392,8 @@ impl Value {
     /// Returns an iterator of `(&Value, &Value)` if `self` is compatible with a map type
     pub fn as_map_iter(&self) -> Option<MapIter<'_>> {
         match self {
-            Value::Bulk(items) => Some(MapIter(items.iter())),
+            Value::Bulk(items) => Some(MapIter(MapIterInner::Flat(items.iter()))),
+            Value::Map(items) => Some(MapIter(MapIterInner::Pairs(items.iter()))),
             _ => None,
         }
     }
@@ -218,6 +422,91 @@ impl fmt::Debug for Value {
             }
             Value::Okay => write!(fmt, "ok"),
             Value::Status(ref s) => write!(fmt, "status({s:?})"),
+            Value::Map(ref values) => {
+                write!(fmt, "map(")?;
+                let mut is_first = true;
+                for (key, val) in values.iter() {
+                    if !is_first {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "{key:?}: {val:?}")?;
+                    is_first = false;
+                }
+                write!(fmt, ")")
+            }
+            Value::Set(ref values) => {
+                write!(fmt, "set(")?;
+                let mut is_first = true;
+                for val in values.iter() {
+                    if !is_first {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "{val:?}")?;
+                    is_first = false;
+                }
+                write!(fmt, ")")
+            }
+        }
+    }
+}
+
+/// The number of bytes shown when displaying a `Value::Data`.
+const DATA_PREVIEW_LEN: usize = 16;
+
+/// A compact, human readable rendering meant for logging. Status replies
+/// (such as the result of `OBJECT ENCODING`) are printed as is, binary data
+/// as its length followed by a hex preview of its first bytes, for instance
+/// `<5 bytes: 68 65 6c 6c 6f>`.
+impl fmt::Display for Value {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_list<'a>(
+            fmt: &mut fmt::Formatter<'_>,
+            values: impl Iterator<Item = &'a Value>,
+        ) -> fmt::Result {
+            for (i, val) in values.enumerate() {
+                if i > 0 {
+                    write!(fmt, ", ")?;
+                }
+                write!(fmt, "{val}")?;
+            }
+            Ok(())
+        }
+
+        match *self {
+            Value::Nil => write!(fmt, "nil"),
+            Value::Int(val) => write!(fmt, "{val}"),
+            Value::Data(ref val) => {
+                write!(fmt, "<{} bytes", val.len())?;
+                for (i, byte) in val.iter().take(DATA_PREVIEW_LEN).enumerate() {
+                    write!(fmt, "{}{byte:02x}", if i == 0 { ": " } else { " " })?;
+                }
+                if val.len() > DATA_PREVIEW_LEN {
+                    write!(fmt, " ...")?;
+                }
+                write!(fmt, ">")
+            }
+            Value::Bulk(ref values) => {
+                write!(fmt, "[")?;
+                write_list(fmt, values.iter())?;
+                write!(fmt, "]")
+            }
+            Value::Okay => write!(fmt, "OK"),
+            Value::Status(ref s) => write!(fmt, "{s}"),
+            Value::Map(ref values) => {
+                write!(fmt, "{{")?;
+                for (i, (key, val)) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, "{key}: {val}")?;
+                }
+                write!(fmt, "}}")
+            }
+            Value::Set(ref values) => {
+                write!(fmt, "{{")?;
+                write_list(fmt, values.iter())?;
+                write!(fmt, "}}")
+            }
         }
     }
 }
@@ -435,6 +724,7 @@ impl RedisError {
             ErrorKind::ExtensionError => "extension error",
             ErrorKind::ClientError => "client error",
             ErrorKind::ReadOnly => "read-only",
+            ErrorKind::TransactionAborted => "transaction aborted",
             #[cfg(feature = "json")]
             ErrorKind::Serialize => "serializing",
         }
@@ -518,6 +808,16 @@ impl RedisError {
         Some((addr, slot_id))
     }
 
+    /// Returns the host and port of the node a `MOVED` or `ASK` error
+    /// redirects to, for instance to route requests without the cluster
+    /// client.
+    pub fn cluster_redirect(&self) -> Option<(String, u16)> {
+        let (addr, _slot_id) = self.redirect_node()?;
+        let (host, port) = addr.rsplit_once(':')?;
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        Some((host.to_string(), port.parse().ok()?))
+    }
+
     /// Returns the extension error code.
     ///
     /// This method should not be used because every time the redis library
@@ -655,6 +955,345 @@ impl Deref for InfoDict {
     }
 }
 
+/// The replication role of a server, as reported by `INFO`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerRole {
+    /// The server is a primary.
+    Master,
+    /// The server replicates a primary.
+    Replica,
+}
+
+/// The reply of the [INFO](https://redis.io/commands/info) command, with
+/// typed accessors for the most common fields.
+///
+/// Unlike [`InfoDict`], values are kept as strings and can be parsed into any
+/// type with [`get`](ServerInfo::get).
+///
+/// ```rust,no_run
+/// # fn do_something() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// use redis::{Commands, ServerInfo};
+/// let info: ServerInfo = con.info(Some("clients"))?;
+/// println!("{:?} clients connected", info.connected_clients());
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerInfo(HashMap<String, String>);
+
+impl ServerInfo {
+    /// Parses the `key:value` lines of an `INFO` reply, skipping the section
+    /// headers.
+    pub fn new(kvpairs: &str) -> ServerInfo {
+        ServerInfo(
+            kvpairs
+                .lines()
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(|line| {
+                    let mut p = line.splitn(2, ':');
+                    Some((p.next()?.to_string(), p.next()?.trim_end().to_string()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Fetches a field and parses it into the given type, `None` if the field
+    /// is missing or can't be parsed.
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.0.get(key).and_then(|v| v.parse().ok())
+    }
+
+    /// The version of the server, such as `7.0.0`.
+    pub fn server_version(&self) -> Option<&str> {
+        self.0.get("redis_version").map(String::as_str)
+    }
+
+    /// The number of seconds since the server started.
+    pub fn uptime_in_seconds(&self) -> Option<u64> {
+        self.get("uptime_in_seconds")
+    }
+
+    /// The number of connected clients, excluding replicas.
+    pub fn connected_clients(&self) -> Option<u64> {
+        self.get("connected_clients")
+    }
+
+    /// The number of bytes allocated by the server.
+    pub fn used_memory(&self) -> Option<u64> {
+        self.get("used_memory")
+    }
+
+    /// The replication role of the server.
+    pub fn role(&self) -> Option<ServerRole> {
+        match self.0.get("role")?.as_str() {
+            "master" => Some(ServerRole::Master),
+            "slave" => Some(ServerRole::Replica),
+            _ => None,
+        }
+    }
+}
+
+impl Deref for ServerInfo {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Details about a single command, as returned by the
+/// [COMMAND INFO](https://redis.io/commands/command-info) command.
+///
+/// ```rust,no_run
+/// # fn do_something() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// use redis::Commands;
+/// let info: Vec<redis::CommandInfo> = con.command_info(&["GET", "SET"])?;
+/// assert_eq!(info[0].arity, 2);
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandInfo {
+    /// The command name, in lower case.
+    pub name: String,
+    /// The number of arguments the command takes. A negative value means the
+    /// command takes at least that many arguments.
+    pub arity: i64,
+    /// The command flags, such as `readonly` or `write`.
+    pub flags: Vec<String>,
+    /// The position of the first key in the argument list.
+    pub first_key: i64,
+    /// The position of the last key in the argument list. A negative value
+    /// counts from the end of the argument list.
+    pub last_key: i64,
+    /// The step between the positions of consecutive keys.
+    pub step: i64,
+    /// The ACL categories the command belongs to (Redis 6.0+).
+    pub acl_categories: Vec<String>,
+}
+
+/// A connected client, as returned by
+/// [CLIENT LIST](https://redis.io/commands/client-list).
+///
+/// ```rust,no_run
+/// # fn do_something() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// use redis::{ClientInfo, ClientType, Commands};
+/// let clients: Vec<ClientInfo> = con.client_list_filtered(ClientType::Normal)?;
+/// for client in clients {
+///     println!("{} connected from {}", client.id, client.addr);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    /// The unique client id.
+    pub id: u64,
+    /// The address and port of the client.
+    pub addr: String,
+    /// The name set with `CLIENT SETNAME`, empty if none.
+    pub name: String,
+    /// The age of the connection in seconds.
+    pub age: u64,
+    /// The idle time of the connection in seconds.
+    pub idle: u64,
+    /// The client flags, such as `N` for a normal client.
+    pub flags: String,
+    /// The currently selected database.
+    pub db: i64,
+    /// The last command the client ran.
+    pub cmd: String,
+    /// All the fields reported by the server, including the ones above.
+    pub fields: HashMap<String, String>,
+}
+
+/// A master monitored by a sentinel, as returned by
+/// [SENTINEL MASTERS](https://redis.io/docs/management/sentinel/#sentinel-commands).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentinelMasterInfo {
+    /// The name the master is monitored as.
+    pub name: String,
+    /// The IP address of the master.
+    pub ip: String,
+    /// The port of the master.
+    pub port: u16,
+    /// The state of the master as seen by the sentinel, such as `master` or
+    /// `master,s_down`.
+    pub flags: String,
+    /// The number of replicas of the master.
+    pub num_slaves: u64,
+    /// The number of other sentinels monitoring the master.
+    pub num_other_sentinels: u64,
+    /// The number of sentinels that need to agree to fail the master over.
+    pub quorum: u64,
+    /// All the fields reported by the sentinel, including the ones above.
+    pub fields: HashMap<String, String>,
+}
+
+/// A replica of a monitored master, as returned by
+/// [SENTINEL SLAVES](https://redis.io/docs/management/sentinel/#sentinel-commands).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentinelSlaveInfo {
+    /// The `ip:port` name of the replica.
+    pub name: String,
+    /// The IP address of the replica.
+    pub ip: String,
+    /// The port of the replica.
+    pub port: u16,
+    /// The state of the replica as seen by the sentinel, such as `slave`.
+    pub flags: String,
+    /// The state of the link to the master, `ok` or `err`.
+    pub master_link_status: String,
+    /// The address of the master the replica replicates from.
+    pub master_host: String,
+    /// The port of the master the replica replicates from.
+    pub master_port: u16,
+    /// All the fields reported by the sentinel, including the ones above.
+    pub fields: HashMap<String, String>,
+}
+
+/// Another sentinel monitoring a master, as returned by
+/// [SENTINEL SENTINELS](https://redis.io/docs/management/sentinel/#sentinel-commands).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentinelInfo {
+    /// The `ip:port` name of the sentinel.
+    pub name: String,
+    /// The IP address of the sentinel.
+    pub ip: String,
+    /// The port of the sentinel.
+    pub port: u16,
+    /// The run id of the sentinel.
+    pub runid: String,
+    /// The state of the sentinel, such as `sentinel`.
+    pub flags: String,
+    /// All the fields reported by the sentinel, including the ones above.
+    pub fields: HashMap<String, String>,
+}
+
+/// A cluster node, as reported by
+/// [CLUSTER SLOTS](https://redis.io/commands/cluster-slots) and
+/// [CLUSTER SHARDS](https://redis.io/commands/cluster-shards).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterNode {
+    /// The preferred endpoint of the node, usually its IP address.
+    pub host: String,
+    /// The port of the node.
+    pub port: u16,
+    /// The node id, if reported by the server (Redis 4.0+).
+    pub id: Option<String>,
+}
+
+/// A range of hash slots and the nodes serving it, as returned by
+/// [CLUSTER SLOTS](https://redis.io/commands/cluster-slots).
+///
+/// ```rust,no_run
+/// # fn do_something() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// use redis::{ClusterSlot, Commands};
+/// let slots: Vec<ClusterSlot> = con.cluster_slots()?;
+/// for slot in slots {
+///     println!("{}-{} served by {}", slot.start, slot.end, slot.primary.host);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterSlot {
+    /// The first slot of the range.
+    pub start: u16,
+    /// The last slot of the range, inclusive.
+    pub end: u16,
+    /// The primary serving the range.
+    pub primary: ClusterNode,
+    /// The replicas of the primary.
+    pub replicas: Vec<ClusterNode>,
+}
+
+/// A shard of a cluster, as returned by
+/// [CLUSTER SHARDS](https://redis.io/commands/cluster-shards) (Redis 7.0+).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterShard {
+    /// The inclusive slot ranges owned by the shard.
+    pub slots: Vec<(u16, u16)>,
+    /// The primary of the shard, `None` if no node currently holds that role.
+    pub primary: Option<ClusterNode>,
+    /// The replicas of the shard.
+    pub replicas: Vec<ClusterNode>,
+}
+
+/// The statistics of the function engines, as returned by
+/// [FUNCTION STATS](https://redis.io/commands/function-stats) (Redis 7.0+).
+///
+/// ```rust,no_run
+/// # fn do_something() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// use redis::{Commands, FunctionStats};
+/// let stats: FunctionStats = con.function_stats()?;
+/// if let Some(script) = stats.running_script {
+///     println!("{} running for {} ms", script.name, script.duration_ms);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionStats {
+    /// The function currently running, if any.
+    pub running_script: Option<RunningScript>,
+    /// The statistics of each engine, by engine name (such as `LUA`).
+    pub engines: HashMap<String, EngineStats>,
+}
+
+/// A function running on the server, part of [`FunctionStats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningScript {
+    /// The name of the function.
+    pub name: String,
+    /// The command and arguments used to call the function.
+    pub command: Vec<String>,
+    /// How long the function has been running, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// The libraries and functions loaded in an engine, part of [`FunctionStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineStats {
+    /// The number of libraries loaded.
+    pub libraries_count: u64,
+    /// The number of functions loaded.
+    pub functions_count: u64,
+}
+
+/// The members popped from a sorted set, as returned by
+/// [ZMPOP](https://redis.io/commands/zmpop) and
+/// [BZMPOP](https://redis.io/commands/bzmpop) (Redis 7.0+).
+///
+/// The commands reply nil when there is nothing to pop, which reads as `None`
+/// into an `Option<ZMPopReply>`.
+///
+/// ```rust,no_run
+/// # fn do_something() -> redis::RedisResult<()> {
+/// # let client = redis::Client::open("redis://127.0.0.1/").unwrap();
+/// # let mut con = client.get_connection().unwrap();
+/// use redis::{Commands, SortOrder, ZMPopReply};
+/// let popped: Option<ZMPopReply> = con.bzmpop(1.0, &["queue"], SortOrder::Min, 10)?;
+/// if let Some(popped) = popped {
+///     for (member, score) in popped.members {
+///         println!("{} popped from {} with score {}", member, popped.key, score);
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZMPopReply {
+    /// The key of the sorted set the members were popped from.
+    pub key: String,
+    /// The popped members with their scores.
+    pub members: Vec<(String, f64)>,
+}
+
 /// Abstraction trait for redis command abstractions.
 pub trait RedisWrite {
     /// Accepts a serialized redis command.
@@ -941,6 +1580,43 @@ impl<T: ToRedisArgs> ToRedisArgs for &T {
     }
 }
 
+// Smart pointers to `str` and slices go through the impls for `&str` and `&[T]`,
+// as `ToRedisArgs` requires `Sized` and can't be implemented for `str` itself.
+macro_rules! pointer_to_redis_impl {
+    ($([$($gen:tt)*] $t:ty),*) => {
+        $(
+            impl<$($gen)*> ToRedisArgs for $t {
+                fn write_redis_args<W>(&self, out: &mut W)
+                where
+                    W: ?Sized + RedisWrite,
+                {
+                    (&**self).write_redis_args(out)
+                }
+
+                fn describe_numeric_behavior(&self) -> NumericBehavior {
+                    (&**self).describe_numeric_behavior()
+                }
+
+                fn is_single_arg(&self) -> bool {
+                    (&**self).is_single_arg()
+                }
+            }
+        )*
+    };
+}
+
+pointer_to_redis_impl!(
+    [T: ToRedisArgs] Box<T>,
+    [T: ToRedisArgs] Arc<T>,
+    [T: ToRedisArgs] Rc<T>,
+    [] Box<str>,
+    [] Arc<str>,
+    [] Rc<str>,
+    [T: ToRedisArgs] Box<[T]>,
+    [T: ToRedisArgs] Arc<[T]>,
+    [T: ToRedisArgs] Rc<[T]>
+);
+
 /// @note: Redis cannot store empty sets so the application has to
 /// check whether the set is empty and if so, not attempt to use that
 /// result
@@ -1238,7 +1914,17 @@ impl<T: FromRedisValue> FromRedisValue for Vec<T> {
                     format!("Conversion to Vec<{}> failed.", std::any::type_name::<T>())
                 ),
             },
-            Value::Bulk(ref items) => FromRedisValue::from_redis_values(items),
+            Value::Bulk(ref items) | Value::Set(ref items) => {
+                FromRedisValue::from_redis_values(items)
+            }
+            // Flatten maps the way RESP2 sends them, so pairs can be read as tuples.
+            Value::Map(ref items) => {
+                let items: Vec<Value> = items
+                    .iter()
+                    .flat_map(|(k, v)| [k.clone(), v.clone()])
+                    .collect();
+                FromRedisValue::from_redis_values(&items)
+            }
             Value::Nil => Ok(vec![]),
             _ => invalid_type_error!(v, "Response type not vector compatible."),
         }
@@ -1411,6 +2097,355 @@ impl FromRedisValue for InfoDict {
     }
 }
 
+impl FromRedisValue for ServerInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<ServerInfo> {
+        let s: String = from_redis_value(v)?;
+        Ok(ServerInfo::new(&s))
+    }
+}
+
+impl FromRedisValue for CommandInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<CommandInfo> {
+        let items = v.as_sequence().ok_or_else(|| {
+            invalid_type_error_inner!(v, "Response type not command info compatible")
+        })?;
+        if items.len() < 6 {
+            invalid_type_error!(v, "Command info response of wrong dimension")
+        }
+        Ok(CommandInfo {
+            name: from_redis_value(&items[0])?,
+            arity: from_redis_value(&items[1])?,
+            flags: from_redis_value(&items[2])?,
+            first_key: from_redis_value(&items[3])?,
+            last_key: from_redis_value(&items[4])?,
+            step: from_redis_value(&items[5])?,
+            acl_categories: match items.get(6) {
+                Some(categories) => from_redis_value(categories)?,
+                None => vec![],
+            },
+        })
+    }
+}
+
+impl ClientInfo {
+    fn parse(line: &str) -> Option<ClientInfo> {
+        let fields: HashMap<String, String> = line
+            .split_whitespace()
+            .filter_map(|field| {
+                let mut p = field.splitn(2, '=');
+                Some((p.next()?.to_string(), p.next()?.to_string()))
+            })
+            .collect();
+        let get = |key: &str| fields.get(key).cloned().unwrap_or_default();
+        let get_num = |key: &str| fields.get(key).and_then(|v| v.parse().ok());
+        Some(ClientInfo {
+            id: get_num("id")?,
+            addr: get("addr"),
+            name: get("name"),
+            age: get_num("age").unwrap_or_default(),
+            idle: get_num("idle").unwrap_or_default(),
+            flags: get("flags"),
+            db: fields
+                .get("db")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default(),
+            cmd: get("cmd"),
+            fields,
+        })
+    }
+}
+
+impl FromRedisValue for ClientInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<ClientInfo> {
+        let s: String = from_redis_value(v)?;
+        match ClientInfo::parse(s.trim()) {
+            Some(info) => Ok(info),
+            None => invalid_type_error!(v, "Response type not client info compatible"),
+        }
+    }
+
+    // `CLIENT LIST` replies with one client per line.
+    fn from_byte_vec(vec: &[u8]) -> Option<Vec<ClientInfo>> {
+        std::str::from_utf8(vec)
+            .ok()?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(ClientInfo::parse)
+            .collect()
+    }
+}
+
+// Reads the fields of an entry of a `SENTINEL` reply, which are all strings.
+fn sentinel_fields(v: &Value) -> RedisResult<HashMap<String, String>> {
+    from_redis_value(v)
+}
+
+// Parses the field `name` of an entry of a `SENTINEL` reply.
+fn sentinel_field<T: FromStr>(fields: &HashMap<String, String>, name: &str) -> RedisResult<T> {
+    match fields.get(name).map(|value| value.parse()) {
+        Some(Ok(value)) => Ok(value),
+        Some(Err(_)) => Err(RedisError::from((
+            ErrorKind::TypeError,
+            "Invalid sentinel field",
+            name.to_string(),
+        ))),
+        None => Err(RedisError::from((
+            ErrorKind::TypeError,
+            "Missing sentinel field",
+            name.to_string(),
+        ))),
+    }
+}
+
+impl FromRedisValue for SentinelMasterInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<SentinelMasterInfo> {
+        let fields = sentinel_fields(v)?;
+        Ok(SentinelMasterInfo {
+            name: sentinel_field(&fields, "name")?,
+            ip: sentinel_field(&fields, "ip")?,
+            port: sentinel_field(&fields, "port")?,
+            flags: sentinel_field(&fields, "flags")?,
+            num_slaves: sentinel_field(&fields, "num-slaves")?,
+            num_other_sentinels: sentinel_field(&fields, "num-other-sentinels")?,
+            quorum: sentinel_field(&fields, "quorum")?,
+            fields,
+        })
+    }
+}
+
+impl FromRedisValue for SentinelSlaveInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<SentinelSlaveInfo> {
+        let fields = sentinel_fields(v)?;
+        Ok(SentinelSlaveInfo {
+            name: sentinel_field(&fields, "name")?,
+            ip: sentinel_field(&fields, "ip")?,
+            port: sentinel_field(&fields, "port")?,
+            flags: sentinel_field(&fields, "flags")?,
+            master_link_status: sentinel_field(&fields, "master-link-status")?,
+            master_host: sentinel_field(&fields, "master-host")?,
+            master_port: sentinel_field(&fields, "master-port")?,
+            fields,
+        })
+    }
+}
+
+impl FromRedisValue for SentinelInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<SentinelInfo> {
+        let fields = sentinel_fields(v)?;
+        Ok(SentinelInfo {
+            name: sentinel_field(&fields, "name")?,
+            ip: sentinel_field(&fields, "ip")?,
+            port: sentinel_field(&fields, "port")?,
+            runid: sentinel_field(&fields, "runid")?,
+            flags: sentinel_field(&fields, "flags")?,
+            fields,
+        })
+    }
+}
+
+impl FromRedisValue for ClusterNode {
+    fn from_redis_value(v: &Value) -> RedisResult<ClusterNode> {
+        let items = v.as_sequence().ok_or_else(|| {
+            invalid_type_error_inner!(v, "Response type not cluster node compatible")
+        })?;
+        if items.len() < 2 {
+            invalid_type_error!(v, "Cluster node response of wrong dimension")
+        }
+        Ok(ClusterNode {
+            host: from_redis_value(&items[0])?,
+            port: from_redis_value(&items[1])?,
+            id: match items.get(2) {
+                Some(id) => from_redis_value(id)?,
+                None => None,
+            },
+        })
+    }
+}
+
+impl FromRedisValue for ClusterSlot {
+    fn from_redis_value(v: &Value) -> RedisResult<ClusterSlot> {
+        let items = v.as_sequence().ok_or_else(|| {
+            invalid_type_error_inner!(v, "Response type not cluster slot compatible")
+        })?;
+        if items.len() < 3 {
+            invalid_type_error!(v, "Cluster slot response of wrong dimension")
+        }
+        Ok(ClusterSlot {
+            start: from_redis_value(&items[0])?,
+            end: from_redis_value(&items[1])?,
+            primary: from_redis_value(&items[2])?,
+            replicas: items[3..]
+                .iter()
+                .map(from_redis_value)
+                .collect::<RedisResult<_>>()?,
+        })
+    }
+}
+
+impl ClusterShard {
+    // Each node of `CLUSTER SHARDS` is a map of its properties; returns the
+    // node along with whether it is the primary.
+    fn parse_node(v: &Value) -> RedisResult<(ClusterNode, bool)> {
+        let fields: HashMap<String, Value> = from_redis_value(v)?;
+        let get = |key: &str| -> RedisResult<Option<String>> {
+            fields.get(key).map(from_redis_value).transpose()
+        };
+        let host = match get("endpoint")?.or(get("ip")?) {
+            Some(host) => host,
+            None => invalid_type_error!(v, "Cluster shard node without endpoint"),
+        };
+        let port = match fields.get("port").or_else(|| fields.get("tls-port")) {
+            Some(port) => from_redis_value(port)?,
+            None => invalid_type_error!(v, "Cluster shard node without port"),
+        };
+        let node = ClusterNode {
+            host,
+            port,
+            id: get("id")?,
+        };
+        Ok((node, get("role")?.as_deref() == Some("master")))
+    }
+}
+
+impl FromRedisValue for ClusterShard {
+    fn from_redis_value(v: &Value) -> RedisResult<ClusterShard> {
+        let fields: HashMap<String, Value> = from_redis_value(v)?;
+        let slots: Vec<u16> = match fields.get("slots") {
+            Some(slots) => from_redis_value(slots)?,
+            None => vec![],
+        };
+        if slots.len() % 2 != 0 {
+            invalid_type_error!(v, "Cluster shard slots of wrong dimension")
+        }
+        let mut shard = ClusterShard {
+            slots: slots.chunks(2).map(|range| (range[0], range[1])).collect(),
+            primary: None,
+            replicas: vec![],
+        };
+        let nodes = fields.get("nodes").and_then(Value::as_sequence);
+        for node in nodes.unwrap_or_default() {
+            match ClusterShard::parse_node(node)? {
+                (node, true) => shard.primary = Some(node),
+                (node, false) => shard.replicas.push(node),
+            }
+        }
+        Ok(shard)
+    }
+}
+
+impl FromRedisValue for FunctionStats {
+    fn from_redis_value(v: &Value) -> RedisResult<FunctionStats> {
+        let fields: HashMap<String, Value> = from_redis_value(v)?;
+        let running_script = match fields.get("running_script") {
+            Some(script) if *script != Value::Nil => Some(from_redis_value(script)?),
+            _ => None,
+        };
+        let engines = match fields.get("engines") {
+            Some(engines) => from_redis_value(engines)?,
+            None => HashMap::new(),
+        };
+        Ok(FunctionStats {
+            running_script,
+            engines,
+        })
+    }
+}
+
+impl FromRedisValue for RunningScript {
+    fn from_redis_value(v: &Value) -> RedisResult<RunningScript> {
+        let fields: HashMap<String, Value> = from_redis_value(v)?;
+        let field = |name: &str| match fields.get(name) {
+            Some(value) => Ok(value),
+            None => Err(RedisError::from((
+                ErrorKind::TypeError,
+                "Missing running script field",
+                name.to_string(),
+            ))),
+        };
+        Ok(RunningScript {
+            name: from_redis_value(field("name")?)?,
+            command: from_redis_value(field("command")?)?,
+            duration_ms: from_redis_value(field("duration_ms")?)?,
+        })
+    }
+}
+
+impl FromRedisValue for EngineStats {
+    fn from_redis_value(v: &Value) -> RedisResult<EngineStats> {
+        let fields: HashMap<String, u64> = from_redis_value(v)?;
+        Ok(EngineStats {
+            libraries_count: fields.get("libraries_count").copied().unwrap_or_default(),
+            functions_count: fields.get("functions_count").copied().unwrap_or_default(),
+        })
+    }
+}
+
+impl FromRedisValue for ZMPopReply {
+    fn from_redis_value(v: &Value) -> RedisResult<ZMPopReply> {
+        let items = match v.as_sequence() {
+            Some(items) if items.len() == 2 => items,
+            _ => invalid_type_error!(v, "Response type not zmpop compatible"),
+        };
+        let members = items[1]
+            .as_sequence()
+            .ok_or_else(|| invalid_type_error_inner!(v, "Response type not zmpop compatible"))?;
+        Ok(ZMPopReply {
+            key: from_redis_value(&items[0])?,
+            members: members
+                .iter()
+                .map(from_redis_value)
+                .collect::<RedisResult<_>>()?,
+        })
+    }
+}
+
+impl FromRedisValue for Encoding {
+    fn from_redis_value(v: &Value) -> RedisResult<Encoding> {
+        let s: String = from_redis_value(v)?;
+        Ok(match s.as_str() {
+            "raw" => Encoding::Raw,
+            "int" => Encoding::Int,
+            "embstr" => Encoding::Embstr,
+            "ziplist" => Encoding::Ziplist,
+            "listpack" => Encoding::Listpack,
+            "linkedlist" => Encoding::Linkedlist,
+            "quicklist" => Encoding::Quicklist,
+            "intset" => Encoding::Intset,
+            "hashtable" => Encoding::Hashtable,
+            "skiplist" => Encoding::Skiplist,
+            "stream" => Encoding::Stream,
+            "json" => Encoding::Json,
+            _ => match s.split_once('-') {
+                Some((module_name, encoding))
+                    if Encoding::MODULE_TYPE_PREFIXES.contains(&module_name) =>
+                {
+                    Encoding::Module {
+                        module_name: module_name.to_string(),
+                        encoding: encoding.to_string(),
+                    }
+                }
+                _ => Encoding::Unknown(s),
+            },
+        })
+    }
+}
+
+impl FromRedisValue for Option<KeyType> {
+    fn from_redis_value(v: &Value) -> RedisResult<Option<KeyType>> {
+        let s: String = from_redis_value(v)?;
+        Ok(Some(match s.as_str() {
+            "none" => return Ok(None),
+            "string" => KeyType::String,
+            "list" => KeyType::List,
+            "set" => KeyType::Set,
+            "zset" => KeyType::ZSet,
+            "hash" => KeyType::Hash,
+            "stream" => KeyType::Stream,
+            _ => KeyType::Unknown(s),
+        }))
+    }
+}
+
 impl<T: FromRedisValue> FromRedisValue for Option<T> {
     fn from_redis_value(v: &Value) -> RedisResult<Option<T>> {
         if *v == Value::Nil {
@@ -1420,6 +2455,20 @@ impl<T: FromRedisValue> FromRedisValue for Option<T> {
     }
 }
 
+macro_rules! pointer_from_redis_impl {
+    ($($t:ident),*) => {
+        $(
+            impl<T: FromRedisValue> FromRedisValue for $t<T> {
+                fn from_redis_value(v: &Value) -> RedisResult<$t<T>> {
+                    Ok($t::new(from_redis_value(v)?))
+                }
+            }
+        )*
+    };
+}
+
+pointer_from_redis_impl!(Box, Arc, Rc);
+
 #[cfg(feature = "bytes")]
 impl FromRedisValue for bytes::Bytes {
     fn from_redis_value(v: &Value) -> RedisResult<Self> {
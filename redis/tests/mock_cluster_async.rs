@@ -273,3 +273,71 @@ fn test_async_cluster_rebuild_with_extra_nodes() {
 
     assert_eq!(value, Ok(Some(123)));
 }
+
+#[test]
+fn test_async_cluster_request_router() {
+    use redis::cluster::RequestRouter;
+
+    let _ = env_logger::try_init();
+    let name = "request_router";
+
+    // Sends the commands mentioning "left" to the first node, and the ones mentioning "right"
+    // to the second.
+    struct LeftRight;
+
+    impl RequestRouter for LeftRight {
+        fn route(&self, cmd: &[u8]) -> Option<usize> {
+            if contains_slice(cmd, b"left") {
+                Some(0)
+            } else if contains_slice(cmd, b"right") {
+                Some(1)
+            } else if contains_slice(cmd, b"lost") {
+                Some(2)
+            } else {
+                None
+            }
+        }
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .enable_time()
+        .build()
+        .unwrap();
+    HANDLERS.write().unwrap().insert(
+        name.to_string(),
+        Arc::new(|cmd, port| {
+            let cmd = cmd.get_packed_command();
+            if contains_slice(&cmd, b"PING") {
+                return Err(Ok(Value::Status("OK".into())));
+            }
+            // Proxies don't support the cluster commands.
+            assert!(!contains_slice(&cmd, b"CLUSTER"));
+            Err(Ok(Value::Int(port.into())))
+        }),
+    );
+    let _handler = RemoveHandler(name.to_string());
+
+    let mut client = Client::open(vec![
+        &*format!("redis://{name}:6379"),
+        &*format!("redis://{name}:6380"),
+    ])
+    .unwrap();
+    let mut connection = runtime
+        .block_on(
+            client
+                .set_request_router(Arc::new(LeftRight))
+                .get_generic_connection::<MockConnection>(),
+        )
+        .unwrap();
+
+    let mut get = |key: &str| runtime.block_on(cmd("GET").arg(key).query_async(&mut connection));
+
+    assert_eq!(get("left"), Ok(6379));
+    assert_eq!(get("right"), Ok(6380));
+    assert!([Ok(6379), Ok(6380)].contains(&get("other")));
+    assert_eq!(
+        get("lost").map_err(|err| err.kind()),
+        Err(redis::ErrorKind::ClientError)
+    );
+}
@@ -25,7 +25,7 @@ impl ::quickcheck::Arbitrary for ArbitraryValue {
 
     fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
         match self.0 {
-            Value::Nil | Value::Okay => Box::new(None.into_iter()),
+            Value::Nil | Value::Okay | Value::Map(_) | Value::Set(_) => Box::new(None.into_iter()),
             Value::Int(i) => Box::new(i.shrink().map(Value::Int).map(ArbitraryValue)),
             Value::Data(ref xs) => Box::new(xs.shrink().map(Value::Data).map(ArbitraryValue)),
             Value::Bulk(ref xs) => {
@@ -14,7 +14,7 @@ use redis::cluster_async::Connect;
 use redis::ConnectionInfo;
 use tempfile::TempDir;
 
-use crate::support::build_keys_and_certs_for_tls;
+use crate::support::{build_keys_and_certs_for_tls, TlsFilePaths};
 
 use super::Module;
 use super::RedisServer;
@@ -56,6 +56,8 @@ impl ClusterType {
 pub struct RedisCluster {
     pub servers: Vec<RedisServer>,
     pub folders: Vec<TempDir>,
+    /// The keys and certificates of the nodes when they use TLS.
+    pub tls_paths: Option<TlsFilePaths>,
 }
 
 impl RedisCluster {
@@ -153,7 +155,11 @@ impl RedisCluster {
         let status = dbg!(cmd).status().unwrap();
         assert!(status.success());
 
-        let cluster = RedisCluster { servers, folders };
+        let cluster = RedisCluster {
+            servers,
+            folders,
+            tls_paths,
+        };
         if replicas > 0 {
             cluster.wait_for_replicas(replicas);
         }
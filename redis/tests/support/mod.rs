@@ -334,6 +334,21 @@ where
         }
         Value::Okay => write!(writer, "+OK\r\n"),
         Value::Status(ref s) => write!(writer, "+{s}\r\n"),
+        Value::Map(ref values) => {
+            write!(writer, "%{}\r\n", values.len())?;
+            for (key, val) in values.iter() {
+                encode_value(key, writer)?;
+                encode_value(val, writer)?;
+            }
+            Ok(())
+        }
+        Value::Set(ref values) => {
+            write!(writer, "~{}\r\n", values.len())?;
+            for val in values.iter() {
+                encode_value(val, writer)?;
+            }
+            Ok(())
+        }
     }
 }
 
@@ -344,6 +359,26 @@ pub struct TlsFilePaths {
     ca_crt: PathBuf,
 }
 
+#[cfg(feature = "tls")]
+impl TlsFilePaths {
+    /// The certificate of the servers, signed by the test CA, as a client identity.
+    pub fn client_identity(&self) -> redis::RedisIdentity {
+        let cert = fs::read(&self.redis_crt).expect("failed to read redis cert");
+        // native-tls wants a PKCS #8 key, which older openssl versions don't generate
+        let key = process::Command::new("openssl")
+            .arg("pkcs8")
+            .arg("-topk8")
+            .arg("-nocrypt")
+            .arg("-in")
+            .arg(&self.redis_key)
+            .stderr(process::Stdio::null())
+            .output()
+            .expect("failed to spawn openssl")
+            .stdout;
+        redis::RedisIdentity::from_pkcs8(&cert, &key).unwrap()
+    }
+}
+
 pub fn build_keys_and_certs_for_tls(tempdir: &TempDir) -> TlsFilePaths {
     // Based on shell script in redis's server tests
     // https://github.com/redis/redis/blob/8c291b97b95f2e011977b522acf77ead23e26f55/utils/gen-test-certs.sh
@@ -2,7 +2,7 @@
 
 use std::collections::HashSet;
 
-use redis::acl::{AclInfo, Rule};
+use redis::acl::{AclInfo, AclLogEntry, Rule};
 use redis::{Commands, Value};
 
 mod support;
@@ -153,3 +153,31 @@ fn test_acl_log() {
     assert_eq!(logs.len(), 0);
     assert_eq!(con.acl_log_reset(), Ok(()));
 }
+
+#[test]
+fn test_acl_log_entries() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+    assert_eq!(con.acl_log_reset(), Ok(()));
+
+    assert_eq!(
+        con.acl_setuser_rules("logged", &[Rule::On, Rule::NoPass, Rule::AllKeys]),
+        Ok(())
+    );
+    // The user has no command permissions, so its GET is denied and logged
+    let mut logged = ctx.connection();
+    let result: redis::RedisResult<()> = redis::cmd("AUTH")
+        .arg("logged")
+        .arg("nopass")
+        .query(&mut logged);
+    assert!(result.is_ok());
+    let result: redis::RedisResult<Option<String>> = logged.get("foo");
+    assert!(result.is_err());
+
+    let logs: Vec<AclLogEntry> = con.acl_log_entries(None).expect("Got logs");
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].count, 1);
+    assert_eq!(logs[0].reason, "command");
+    assert_eq!(logs[0].object, "get");
+    assert_eq!(logs[0].username, "logged");
+}
@@ -30,6 +30,48 @@ fn test_args() {
     .unwrap();
 }
 
+#[test]
+fn test_select_db() {
+    use redis::aio::ConnectionLike;
+    use redis::RedisError;
+
+    let ctx = TestContext::new();
+    block_on_all(async move {
+        let mut con = ctx.async_connection().await?;
+        let () = con.set("foo", 1).await?;
+
+        con.select_db(1).await?;
+        assert_eq!(con.get_db(), 1);
+        assert_eq!(con.exists("foo").await, Ok(false));
+
+        con.select_db(0).await?;
+        assert_eq!(con.get_db(), 0);
+        assert_eq!(con.get("foo").await, Ok(1));
+
+        Ok::<_, RedisError>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_object_encoding() {
+    use redis::{Encoding, RedisError};
+
+    let ctx = TestContext::new();
+    block_on_all(async move {
+        let mut con = ctx.async_connection().await?;
+        let () = con.set("int", 42).await?;
+        let () = con.set("str", "value").await?;
+
+        assert_eq!(con.object_encoding("int").await, Ok(Encoding::Int));
+        assert_eq!(con.object_encoding("str").await, Ok("embstr".to_string()));
+        assert_eq!(con.object_encoding("missing").await, Ok(None::<Encoding>));
+
+        Ok::<_, RedisError>(())
+    })
+    .unwrap();
+}
+
 #[test]
 fn dont_panic_on_closed_multiplexed_connection() {
     let ctx = TestContext::new();
@@ -193,6 +235,34 @@ fn test_args_multiplexed_connection() {
     .unwrap();
 }
 
+#[test]
+fn test_multiplexed_connection_stats() {
+    use redis::RedisError;
+
+    let ctx = TestContext::new();
+    block_on_all(async move {
+        let mut con = ctx.multiplexed_async_connection().await?;
+        let before = con.stats();
+
+        let () = con.set("key", 42).await?;
+        let (value,): (i32,) = redis::pipe()
+            .atomic()
+            .get("key")
+            .query_async(&mut con)
+            .await?;
+        assert_eq!(value, 42);
+
+        let stats = con.stats();
+        assert_eq!(stats.pending_requests, 0);
+        // SET, then MULTI, GET and EXEC
+        assert_eq!(stats.commands_sent - before.commands_sent, 4);
+        assert!(stats.bytes_sent > before.bytes_sent);
+        assert!(stats.bytes_received > before.bytes_received);
+        Ok::<_, RedisError>(())
+    })
+    .unwrap();
+}
+
 #[test]
 fn test_args_with_errors_multiplexed_connection() {
     let ctx = TestContext::new();
@@ -441,13 +511,11 @@ async fn io_error_on_kill_issue_320() {
 #[tokio::test]
 async fn invalid_password_issue_343() {
     let ctx = TestContext::new();
+    let mut redis = redis::RedisConnectionInfo::default();
+    redis.password = Some("asdcasc".to_string());
     let coninfo = redis::ConnectionInfo {
         addr: ctx.server.client_addr().clone(),
-        redis: redis::RedisConnectionInfo {
-            db: 0,
-            username: None,
-            password: Some("asdcasc".to_string()),
-        },
+        redis,
     };
     let client = redis::Client::open(coninfo).unwrap();
     let err = client
@@ -462,6 +530,84 @@ async fn invalid_password_issue_343() {
     );
 }
 
+#[tokio::test]
+async fn test_pipeline_query_async_with_timeout() {
+    use std::time::Duration;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.async_connection().await.unwrap();
+
+    let (value,): (i32,) = redis::pipe()
+        .set("key", 42)
+        .ignore()
+        .get("key")
+        .query_async_with_timeout(&mut con, Duration::from_secs(1))
+        .await
+        .unwrap();
+    assert_eq!(value, 42);
+
+    // Blocks for a second on the server
+    let err = redis::pipe()
+        .blpop("missing", 1)
+        .query_async_with_timeout::<_, ()>(&mut con, Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+
+    // The late reply would be mistaken for the one of the next request
+    let result: RedisResult<i32> = con.get("key").await;
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "connection-manager")]
+#[tokio::test]
+async fn test_connection_manager_reconnects_after_timeout() {
+    use std::time::Duration;
+
+    let ctx = TestContext::new();
+    let mut manager = redis::aio::ConnectionManager::new(ctx.client.clone())
+        .await
+        .unwrap();
+
+    let err = redis::pipe()
+        .blpop("missing", 1)
+        .query_async_with_timeout::<_, ()>(&mut manager, Duration::from_millis(50))
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+
+    let () = manager.set("key", 42).await.unwrap();
+    assert_eq!(manager.get("key").await, Ok(42));
+}
+
+#[cfg(feature = "connection-manager")]
+#[tokio::test]
+async fn test_connection_manager_with_connection_factory() {
+    let ctx = TestContext::new();
+    let client = ctx.client.clone();
+    let mut manager = redis::aio::ConnectionManager::with_connection_factory(client, |client| {
+        let client = client.clone();
+        async move {
+            let mut con = client.get_multiplexed_tokio_connection().await?;
+            cmd("CLIENT")
+                .arg("SETNAME")
+                .arg("factory")
+                .query_async::<_, ()>(&mut con)
+                .await?;
+            Ok(con)
+        }
+    })
+    .await
+    .unwrap();
+
+    let name: String = cmd("CLIENT")
+        .arg("GETNAME")
+        .query_async(&mut manager)
+        .await
+        .unwrap();
+    assert_eq!(name, "factory");
+}
+
 // Test issue of Stream trait blocking if we try to iterate more than 10 items
 // https://github.com/mitsuhiko/redis-rs/issues/537 and https://github.com/mitsuhiko/redis-rs/issues/583
 #[tokio::test]
@@ -499,6 +645,42 @@ async fn test_issue_async_commands_scan_broken() {
     assert_eq!(keys.len(), 100);
 }
 
+#[tokio::test]
+async fn test_scan_match_type() {
+    use redis::KeyType;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.async_connection().await.unwrap();
+    for i in 0..20 {
+        let _: () = con.set(format!("async:string{i}"), i).await.unwrap();
+        let _: () = con.sadd(format!("async:set{i}"), i).await.unwrap();
+    }
+
+    let iter: redis::AsyncIter<String> =
+        con.scan_match_type("async:*", KeyType::Set).await.unwrap();
+    let mut keys: Vec<String> = iter.collect().await;
+    keys.sort();
+    let mut expected: Vec<String> = (0..20).map(|i| format!("async:set{i}")).collect();
+    expected.sort();
+    assert_eq!(keys, expected);
+}
+
+#[tokio::test]
+async fn test_unlink() {
+    let ctx = TestContext::new();
+    let mut con = ctx.async_connection().await.unwrap();
+
+    let _: () = con.set("foo", 42).await.unwrap();
+    assert_eq!(con.unlink("foo").await, Ok(true));
+
+    let _: () = con.set("foo", 42).await.unwrap();
+    let _: () = con.set("bar", 42).await.unwrap();
+    assert_eq!(
+        con.unlink_multiple(&["foo", "bar", "baz"]).await,
+        Ok(2usize)
+    );
+}
+
 mod pub_sub {
     use std::collections::HashMap;
     use std::time::Duration;
@@ -525,6 +707,68 @@ mod pub_sub {
         .unwrap();
     }
 
+    #[test]
+    fn pub_sub_listen() {
+        use redis::RedisError;
+
+        let ctx = TestContext::new();
+        block_on_all(async move {
+            let mut messages = ctx.client.pubsub_listen(&["foo", "bar"]).await?;
+            let mut publish_conn = ctx.async_connection().await?;
+            publish_conn.publish("foo", "first").await?;
+            publish_conn.publish("bar", "second").await?;
+
+            let msg = messages.next().await.unwrap()?;
+            assert_eq!(msg.get_channel_name(), "foo");
+            assert_eq!(msg.get_payload::<String>()?, "first");
+
+            let msg = messages.next().await.unwrap()?;
+            assert_eq!(msg.get_channel_name(), "bar");
+            assert_eq!(msg.get_payload::<String>()?, "second");
+
+            Ok::<_, RedisError>(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn pub_sub_subscribe_awaits_confirmation() {
+        use redis::RedisError;
+
+        let ctx = TestContext::new();
+        block_on_all(async move {
+            let mut pubsub_conn = ctx.async_connection().await?.into_pubsub();
+            pubsub_conn
+                .subscribe(&["confirmed-1", "confirmed-2"])
+                .await?;
+
+            let mut conn = ctx.async_connection().await?;
+            let subscriptions_counts: HashMap<String, u32> = redis::cmd("PUBSUB")
+                .arg("NUMSUB")
+                .arg(&["confirmed-1", "confirmed-2"])
+                .query_async(&mut conn)
+                .await?;
+            assert_eq!(subscriptions_counts.get("confirmed-1"), Some(&1));
+            assert_eq!(subscriptions_counts.get("confirmed-2"), Some(&1));
+
+            // A message published before the next confirmation is not lost.
+            conn.publish("confirmed-1", "early").await?;
+            pubsub_conn.psubscribe("confirmed-*").await?;
+            conn.publish("confirmed-2", "late").await?;
+
+            let mut pubsub_stream = pubsub_conn.on_message();
+            let msg = pubsub_stream.next().await.unwrap();
+            assert_eq!(msg.get_channel_name(), "confirmed-1");
+            assert_eq!(msg.get_payload::<String>()?, "early");
+            let msg = pubsub_stream.next().await.unwrap();
+            assert_eq!(msg.get_channel_name(), "confirmed-2");
+            assert_eq!(msg.get_payload::<String>()?, "late");
+
+            Ok::<_, RedisError>(())
+        })
+        .unwrap();
+    }
+
     #[test]
     fn pub_sub_unsubscription() {
         use redis::RedisError;
@@ -1,8 +1,9 @@
 #![allow(clippy::let_unit_value)]
 
 use redis::{
-    Commands, ConnectionInfo, ConnectionLike, ControlFlow, ErrorKind, Expiry, PubSubCommands,
-    RedisResult,
+    CommandListFilter, Commands, ConnectionInfo, ConnectionLike, ControlFlow, EngineStats,
+    ErrorKind, Expiry, FunctionStats, MigrateOptions, PubSubCommands, RangeMode, RedisResult,
+    RestorePolicy, ScanOptions, SortOrder, ZMPopReply, ZRangeOptions, ZaddOptions,
 };
 
 use std::collections::{BTreeMap, BTreeSet};
@@ -57,6 +58,81 @@ fn test_getset() {
     );
 }
 
+#[test]
+fn test_set_get() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.set_get("foo", 42), Ok(None::<usize>));
+    assert_eq!(con.set_get("foo", 43), Ok(Some(42usize)));
+    assert_eq!(con.get("foo"), Ok(43usize));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_getset_command() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.getset("foo", 42), Ok(None::<usize>));
+    assert_eq!(con.getset("foo", 43), Ok(Some(42usize)));
+    assert_eq!(con.get("foo"), Ok(43usize));
+}
+
+#[test]
+fn test_setrange_and_return() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set("key", "Hello World").unwrap();
+    assert_eq!(
+        con.setrange_and_return("key", 6, "Redis"),
+        Ok(b"Hello Redis".to_vec())
+    );
+    // Missing keys and gaps are padded with zero bytes.
+    assert_eq!(
+        con.setrange_and_return("missing", 2, &[0xffu8][..]),
+        Ok(vec![0, 0, 0xff])
+    );
+}
+
+#[test]
+fn test_getrange_utf8() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let () = con.set("foo", "héllo wörld 😀!").unwrap();
+    assert_eq!(con.getrange_utf8("foo", 1, 4), Ok("éllo".to_string()));
+    assert_eq!(con.getrange_utf8("foo", 12, 12), Ok("😀".to_string()));
+    assert_eq!(con.getrange_utf8("foo", 10, 100), Ok("d 😀!".to_string()));
+    assert_eq!(con.getrange_utf8("foo", 5, 2), Ok("".to_string()));
+    assert_eq!(con.getrange_utf8("missing", 0, 2), Ok("".to_string()));
+
+    let () = con.set("bytes", &[0xffu8, 0xfe][..]).unwrap();
+    assert!(con.getrange_utf8("bytes", 0, 1).is_err());
+}
+
+#[test]
+fn test_select_db() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let () = con.set("foo", 1).unwrap();
+    assert_eq!(con.get_db(), 0);
+
+    con.select_db(1).unwrap();
+    assert_eq!(con.get_db(), 1);
+    assert_eq!(con.exists("foo"), Ok(false));
+
+    con.select_db(0).unwrap();
+    assert_eq!(con.get_db(), 0);
+    assert_eq!(con.get("foo"), Ok(1));
+
+    // An invalid index leaves the connection where it was
+    assert!(con.select_db(100_000).is_err());
+    assert_eq!(con.get_db(), 0);
+}
+
 #[test]
 fn test_incr() {
     let ctx = TestContext::new();
@@ -115,6 +191,22 @@ fn test_getex() {
     assert_eq!(delayed_get, 420usize);
 }
 
+#[test]
+fn test_set_keepttl() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set_ex("foo", 1, 100).unwrap();
+    let _: () = con.set_keepttl("foo", 2).unwrap();
+    assert_eq!(con.get("foo"), Ok(2));
+    let ttl: isize = con.ttl("foo").unwrap();
+    assert!(ttl > 0 && ttl <= 100);
+
+    // a plain SET still clears the TTL
+    let _: () = con.set("foo", 3).unwrap();
+    assert_eq!(con.ttl("foo"), Ok(-1));
+}
+
 #[test]
 fn test_info() {
     let ctx = TestContext::new();
@@ -131,6 +223,24 @@ fn test_info() {
     assert!(info.contains_key(&"role"));
 }
 
+#[test]
+fn test_server_info() {
+    use redis::{ServerInfo, ServerRole};
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let info: ServerInfo = con.info(None::<&str>).unwrap();
+    assert!(info.server_version().is_some());
+    assert_eq!(info.role(), Some(ServerRole::Master));
+    assert!(info.connected_clients().unwrap() >= 1);
+    assert!(info.used_memory().unwrap() > 0);
+
+    let info: ServerInfo = con.info(Some("clients")).unwrap();
+    assert!(info.connected_clients().is_some());
+    assert_eq!(info.server_version(), None);
+}
+
 #[test]
 fn test_hash_ops() {
     let ctx = TestContext::new();
@@ -158,6 +268,92 @@ fn test_hash_ops() {
     assert_eq!(h.get("key_2"), Some(&2i32));
 }
 
+#[test]
+fn test_hset_nx_multiple() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.hset("hash", "b", 1).unwrap();
+    assert_eq!(
+        con.hset_nx_multiple("hash", &[("a", 10), ("b", 20), ("c", 30)]),
+        Ok(vec![true, false, true])
+    );
+    assert_eq!(
+        con.hgetall("hash"),
+        Ok(HashMap::from([
+            ("a".to_string(), 10),
+            ("b".to_string(), 1),
+            ("c".to_string(), 30),
+        ]))
+    );
+
+    let empty: [(&str, i32); 0] = [];
+    assert_eq!(con.hset_nx_multiple("hash", &empty), Ok(vec![]));
+}
+
+#[test]
+fn test_hincrbyfloat() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.hincrbyfloat("foo", "field", 1.5), Ok(1.5));
+    assert_eq!(con.hincrbyfloat("foo", "field", -4.0), Ok(-2.5));
+
+    let err = con.hincrbyfloat("foo", "field", f64::NAN).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidClientConfig);
+    let err = con.hincrbyfloat("foo", "field", f64::INFINITY).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidClientConfig);
+    assert_eq!(con.hget("foo", "field"), Ok(-2.5));
+}
+
+#[test]
+fn test_incrbyfloat() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.incrbyfloat("foo", 0.5), Ok(0.5));
+    assert_eq!(con.incrbyfloat("foo", -1.25), Ok(-0.75));
+
+    let err = con.incrbyfloat("foo", f64::NAN).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidClientConfig);
+    let err = con.incrbyfloat("foo", f64::NEG_INFINITY).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidClientConfig);
+
+    // very small increments survive the round trip
+    assert_eq!(con.incrbyfloat("small", 1e-10), Ok(1e-10));
+    assert_eq!(con.incrbyfloat("small", -1e-10), Ok(0.0));
+
+    // very large values survive the round trip
+    assert_eq!(con.incrbyfloat("big", 1e300), Ok(1e300));
+}
+
+#[test]
+fn test_hgetall_as_struct() {
+    use redis::{FromRedisValue, Value};
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl FromRedisValue for Point {
+        fn from_redis_value(v: &Value) -> RedisResult<Point> {
+            let h: HashMap<String, i32> = redis::from_redis_value(v)?;
+            match (h.get("x"), h.get("y")) {
+                (Some(&x), Some(&y)) => Ok(Point { x, y }),
+                _ => Err((ErrorKind::TypeError, "Missing point coordinates").into()),
+            }
+        }
+    }
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.hset_multiple("point", &[("x", 3), ("y", 4)]).unwrap();
+    assert_eq!(con.hgetall_as_struct("point"), Ok(Point { x: 3, y: 4 }));
+}
+
 // Requires redis-server >= 4.0.0.
 // Not supported with the current appveyor/windows binary deployed.
 #[cfg(not(target_os = "windows"))]
@@ -173,6 +369,63 @@ fn test_unlink() {
     redis::cmd("SET").arg("foo").arg(42).execute(&mut con);
     redis::cmd("SET").arg("bar").arg(42).execute(&mut con);
     assert_eq!(con.unlink(&["foo", "bar"]), Ok(2));
+
+    redis::cmd("SET").arg("foo").arg(42).execute(&mut con);
+    assert_eq!(con.unlink("foo"), Ok(true));
+    assert_eq!(con.unlink("foo"), Ok(false));
+
+    redis::cmd("SET").arg("foo").arg(42).execute(&mut con);
+    redis::cmd("SET").arg("bar").arg(42).execute(&mut con);
+    assert_eq!(con.unlink_multiple(&["foo", "bar", "baz"]), Ok(2usize));
+}
+
+#[test]
+fn test_migrate() {
+    let ctx = TestContext::new();
+    let target = TestContext::new();
+    let (host, port) = match target.server.client_addr() {
+        redis::ConnectionAddr::Tcp(host, port) => (host.clone(), *port),
+        // MIGRATE can only reach the target over TCP.
+        _ => return,
+    };
+    let mut con = ctx.connection();
+    let mut target_con = target.connection();
+
+    let () = con.set("foo", 1).unwrap();
+    let () = con.set("bar", 2).unwrap();
+    let () = con.set("baz", 3).unwrap();
+
+    let opts = MigrateOptions::default();
+    assert_eq!(
+        con.migrate(&host, port, "foo", 0, 1000, opts),
+        Ok("OK".to_string())
+    );
+    assert_eq!(con.exists("foo"), Ok(false));
+    assert_eq!(target_con.get("foo"), Ok(1));
+
+    let opts = MigrateOptions::default().copy().keys(&["bar", "baz"]);
+    assert_eq!(
+        con.migrate(&host, port, "", 0, 1000, opts),
+        Ok("OK".to_string())
+    );
+    assert_eq!(con.exists(&["bar", "baz"]), Ok(2));
+    assert_eq!(target_con.get(&["bar", "baz"]), Ok((2, 3)));
+
+    // Without REPLACE, keys that already exist on the target are an error.
+    let opts = MigrateOptions::default().copy().keys(&["bar"]);
+    let result: RedisResult<String> = con.migrate(&host, port, "", 0, 1000, opts);
+    assert!(result.is_err());
+    let opts = MigrateOptions::default().copy().replace().keys(&["bar"]);
+    assert_eq!(
+        con.migrate(&host, port, "", 0, 1000, opts),
+        Ok("OK".to_string())
+    );
+
+    let opts = MigrateOptions::default();
+    assert_eq!(
+        con.migrate(&host, port, "missing", 0, 1000, opts),
+        Ok("NOKEY".to_string())
+    );
 }
 
 #[test]
@@ -198,6 +451,31 @@ fn test_set_ops() {
     assert!(set.contains(&1i32));
     assert!(set.contains(&2i32));
     assert!(set.contains(&3i32));
+
+    let set: HashSet<i32> = con.smembers_as_set("foo").unwrap();
+    assert_eq!(set, [1, 2, 3].into_iter().collect());
+
+    let member: Option<i32> = con.srandmember("foo").unwrap();
+    assert!(matches!(member, Some(1..=3)));
+    assert_eq!(con.srandmember::<_, i32>("missing"), Ok(None));
+}
+
+#[test]
+fn test_set_store_ops() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.sadd("a", &[1, 2, 3, 4]), Ok(4));
+    assert_eq!(con.sadd("b", &[3, 4, 5]), Ok(3));
+
+    assert_eq!(con.sinterstore("inter", &["a", "b"]), Ok(2usize));
+    assert_eq!(con.sdiffstore("diff", &["a", "b"]), Ok(2usize));
+    assert_eq!(con.sunionstore("union", &["a", "b"]), Ok(5usize));
+    assert_eq!(con.sinterstore("empty", &["a", "missing"]), Ok(0usize));
+
+    let diff: HashSet<i32> = con.smembers("diff").unwrap();
+    assert_eq!(diff, [1, 2].into_iter().collect());
+    assert_eq!(con.exists("empty"), Ok(false));
 }
 
 #[test]
@@ -218,6 +496,30 @@ fn test_scan() {
     assert_eq!(&s, &[1, 2, 3]);
 }
 
+#[test]
+fn test_scan_match_type() {
+    use redis::KeyType;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set("scan:string", 1).unwrap();
+    let _: () = con.sadd("scan:set", 1).unwrap();
+    let _: () = con.sadd("other:set", 1).unwrap();
+
+    let keys: Vec<String> = con
+        .scan_match_type("scan:*", KeyType::Set)
+        .unwrap()
+        .collect();
+    assert_eq!(keys, vec!["scan:set"]);
+
+    let keys: Vec<String> = con
+        .scan_match_type("scan:*", KeyType::Hash)
+        .unwrap()
+        .collect();
+    assert!(keys.is_empty());
+}
+
 #[test]
 fn test_optionals() {
     let ctx = TestContext::new();
@@ -293,6 +595,26 @@ fn test_filtered_scanning() {
     assert_eq!(unseen.len(), 0);
 }
 
+#[test]
+fn test_hscan_options() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    for x in 0..1000 {
+        let _: () = con.hset("foo", format!("key_{}_{}", x % 10, x), x).unwrap();
+    }
+
+    let opts = ScanOptions::default()
+        .with_pattern("key_3_*")
+        .with_count(50);
+    let found: HashSet<(String, usize)> = con.hscan_options("foo", opts).unwrap().collect();
+
+    assert_eq!(found.len(), 100);
+    assert!(found
+        .iter()
+        .all(|(field, value)| value % 10 == 3 && field == &format!("key_3_{value}")));
+}
+
 #[test]
 fn test_pipeline() {
     let ctx = TestContext::new();
@@ -316,6 +638,22 @@ fn test_pipeline() {
     assert_eq!(k2, 43);
 }
 
+#[test]
+fn test_pipeline_large_tuple() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let mut pipe = redis::pipe();
+    for i in 0..10 {
+        pipe.incr(format!("counter_{i}"), i);
+    }
+    let (a, b, c, d, e, f, g, h, i, j): (i32, i32, i32, i32, i32, i32, i32, i32, i32, i32) =
+        pipe.query(&mut con).unwrap();
+
+    assert_eq!((a, b, c, d, e), (0, 1, 2, 3, 4));
+    assert_eq!((f, g, h, i, j), (5, 6, 7, 8, 9));
+}
+
 #[test]
 fn test_pipeline_with_err() {
     let ctx = TestContext::new();
@@ -415,6 +753,37 @@ fn test_pipeline_transaction_with_errors() {
     assert_eq!(x, 42);
 }
 
+#[test]
+fn test_pipeline_atomic_with_watch() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+    let mut other = ctx.connection();
+
+    let (x,): (i32,) = redis::Pipeline::atomic_with_watch(&["x"])
+        .incr("x", 1)
+        .query(&mut con)
+        .unwrap();
+    assert_eq!(x, 1);
+
+    // Watches accumulate until EXEC, so a key modified by another client
+    // after an earlier WATCH aborts the transaction.
+    let () = redis::cmd("WATCH").arg("y").query(&mut con).unwrap();
+    let () = other.set("y", 1).unwrap();
+    let err: RedisResult<(i32,)> = redis::Pipeline::atomic_with_watch(&["x"])
+        .incr("x", 1)
+        .query(&mut con);
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::TransactionAborted);
+    assert_eq!(con.get("x"), Ok(1));
+
+    // Clearing the pipeline drops the watched keys too
+    let mut pl = redis::Pipeline::atomic_with_watch(&["x"]);
+    pl.clear();
+    let (x,): (i32,) = pl.atomic().incr("x", 1).query(&mut con).unwrap();
+    assert_eq!(x, 2);
+    let packed = String::from_utf8(pl.get_packed_pipeline()).unwrap();
+    assert!(!packed.contains("WATCH"));
+}
+
 #[test]
 fn test_pipeline_reuse_query() {
     let ctx = TestContext::new();
@@ -654,6 +1023,65 @@ fn test_pubsub_unsubscribe_one_sub_one_psub() {
     assert_eq!(&value[..], "bar");
 }
 
+#[test]
+fn test_pubsub_get_message_timeout() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+    let mut pubsub_con = ctx.connection();
+
+    let mut pubsub = pubsub_con.as_pubsub();
+    pubsub.subscribe("foo").unwrap();
+
+    let msg = pubsub
+        .get_message_timeout(Duration::from_millis(10))
+        .unwrap();
+    assert!(msg.is_none());
+
+    assert_eq!(con.publish("foo", 42), Ok(1));
+    let msg = pubsub
+        .get_message_timeout(Duration::from_millis(1000))
+        .unwrap()
+        .unwrap();
+    assert_eq!(msg.get_channel(), Ok("foo".to_string()));
+    assert_eq!(msg.get_payload(), Ok(42));
+}
+
+#[test]
+fn test_pubsub_psubscribe_guarded() {
+    use std::sync::{Arc, Barrier};
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let mut pubsub_con = ctx.connection();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let pubsub_barrier = barrier.clone();
+
+    let thread = spawn(move || {
+        let mut pubsub = pubsub_con.as_pubsub();
+        {
+            let mut subscription = pubsub.psubscribe_guarded("foo*").unwrap();
+
+            let _ = pubsub_barrier.wait();
+
+            let msg = subscription.messages().next().unwrap();
+            assert_eq!(msg.get_channel(), Ok("foo.bar".to_string()));
+            assert_eq!(msg.get_pattern(), Ok("foo*".to_string()));
+            assert_eq!(msg.get_payload(), Ok(42));
+        }
+        let _ = pubsub_barrier.wait();
+    });
+
+    let _ = barrier.wait();
+    assert_eq!(con.publish("foo.bar", 42), Ok(1));
+
+    let _ = barrier.wait();
+    let numpat: usize = redis::cmd("PUBSUB").arg("NUMPAT").query(&mut con).unwrap();
+    assert_eq!(numpat, 0);
+
+    thread.join().expect("Something went wrong");
+}
+
 #[test]
 fn scoped_pubsub() {
     let ctx = TestContext::new();
@@ -735,6 +1163,7 @@ fn test_script_load() {
     let hash = script.prepare_invoke().load(&mut con);
 
     assert_eq!(hash, Ok(script.get_hash().to_string()));
+    assert_eq!(script.sha1(), script.get_hash());
 }
 
 #[test]
@@ -905,6 +1334,69 @@ fn test_bit_operations() {
     assert_eq!(con.getbit("bitvec", 10), Ok(true));
 }
 
+#[test]
+fn test_bitpos() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    // 0xff 0xf0 0x00 0x81
+    let _: () = con.set("bits", &[0xffu8, 0xf0, 0x00, 0x81][..]).unwrap();
+
+    assert_eq!(con.bitpos("bits", true, None, None, None), Ok(0));
+    assert_eq!(con.bitpos("bits", false, None, None, None), Ok(12));
+    assert_eq!(con.bitpos("bits", true, Some(2), None, None), Ok(24));
+    assert_eq!(con.bitpos("bits", false, Some(1), Some(1), None), Ok(12));
+    assert_eq!(con.bitpos("bits", true, Some(2), Some(2), None), Ok(-1));
+    assert_eq!(con.bitpos("empty", true, None, None, None), Ok(-1));
+
+    if con.server_version().unwrap() < (7, 0, 0) {
+        return;
+    }
+
+    let bit = Some(RangeMode::Bit);
+    assert_eq!(con.bitpos("bits", true, Some(9), Some(15), bit), Ok(9));
+    assert_eq!(con.bitpos("bits", false, Some(0), Some(7), bit), Ok(-1));
+    assert_eq!(con.bitpos("bits", false, Some(-8), Some(-1), bit), Ok(25));
+    assert_eq!(
+        con.bitpos("bits", true, Some(1), Some(1), Some(RangeMode::Byte)),
+        Ok(8)
+    );
+}
+
+#[test]
+fn test_bitcount_range_mode() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if con.server_version().unwrap() < (7, 0, 0) {
+        return;
+    }
+
+    // 0xff 0xf0 0x00 0x81
+    let _: () = con.set("bits", &[0xffu8, 0xf0, 0x00, 0x81][..]).unwrap();
+
+    assert_eq!(
+        con.bitcount_range_mode("bits", 0, 0, RangeMode::Byte),
+        Ok(8)
+    );
+    assert_eq!(
+        con.bitcount_range_mode("bits", 1, -1, RangeMode::Byte),
+        Ok(6)
+    );
+    assert_eq!(
+        con.bitcount_range_mode("bits", 4, 11, RangeMode::Bit),
+        Ok(8)
+    );
+    assert_eq!(
+        con.bitcount_range_mode("bits", -8, -1, RangeMode::Bit),
+        Ok(2)
+    );
+    assert_eq!(
+        con.bitcount_range_mode("bits", 16, 23, RangeMode::Bit),
+        Ok(0)
+    );
+}
+
 #[test]
 fn test_redis_server_down() {
     let mut ctx = TestContext::new();
@@ -1064,35 +1556,258 @@ fn test_zrembylex() {
     assert_eq!(remaining, vec!["apple".to_string(), "grapes".to_string()]);
 }
 
-// Requires redis-server >= 6.2.0.
-// Not supported with the current appveyor/windows binary deployed.
-#[cfg(not(target_os = "windows"))]
 #[test]
-fn test_zrandmember() {
+fn test_wait() {
     let ctx = TestContext::new();
     let mut con = ctx.connection();
 
-    let setname = "myzrandset";
-    let () = con.zadd(setname, "one", 1).unwrap();
+    let () = con.set("foo", 42).unwrap();
+    // There are no replicas, so this returns straight away
+    assert_eq!(con.wait(0, 0), Ok(0));
 
-    let result: String = con.zrandmember(setname, None).unwrap();
-    assert_eq!(result, "one".to_string());
+    let version = con.server_version().unwrap();
+    assert!(version >= (6, 0, 0));
+    if version >= (7, 2, 0) {
+        // The test server doesn't have AOF enabled, so nothing can be acked
+        assert_eq!(con.waitaof(0, 0, 0), Ok((0, 0)));
+    }
+}
 
-    let result: Vec<String> = con.zrandmember(setname, Some(1)).unwrap();
-    assert_eq!(result.len(), 1);
-    assert_eq!(result[0], "one".to_string());
+#[test]
+fn test_lolwut() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
 
-    let result: Vec<String> = con.zrandmember(setname, Some(2)).unwrap();
-    assert_eq!(result.len(), 1);
-    assert_eq!(result[0], "one".to_string());
+    let version = con.server_version().unwrap();
+    let art: String = con.lolwut(None).unwrap();
+    assert!(art.contains(&format!("{}.{}.{}", version.0, version.1, version.2)));
 
-    assert_eq!(
-        con.zadd_multiple(
-            setname,
-            &[(2, "two"), (3, "three"), (4, "four"), (5, "five")]
-        ),
-        Ok(4)
-    );
+    let art: String = con.lolwut(Some(5)).unwrap();
+    assert!(!art.is_empty());
+}
+
+#[test]
+fn test_functions() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if con.server_version().unwrap() < (7, 0, 0) {
+        return;
+    }
+
+    let () = redis::cmd("FUNCTION")
+        .arg("LOAD")
+        .arg("#!lua name=mylib\nredis.register_function('echo', function(keys, args) return args[1] end)")
+        .query(&mut con)
+        .unwrap();
+
+    let stats: FunctionStats = con.function_stats().unwrap();
+    assert_eq!(stats.running_script, None);
+    assert_eq!(
+        stats.engines.get("LUA"),
+        Some(&EngineStats {
+            libraries_count: 1,
+            functions_count: 1,
+        })
+    );
+
+    let payload: Vec<u8> = con.function_dump().unwrap();
+    let () = redis::cmd("FUNCTION").arg("FLUSH").query(&mut con).unwrap();
+    let stats: FunctionStats = con.function_stats().unwrap();
+    assert_eq!(stats.engines["LUA"].libraries_count, 0);
+
+    let () = con
+        .function_restore(&payload, RestorePolicy::Append)
+        .unwrap();
+    let echoed: String = redis::cmd("FCALL")
+        .arg("echo")
+        .arg(0)
+        .arg("hello")
+        .query(&mut con)
+        .unwrap();
+    assert_eq!(echoed, "hello");
+
+    // The library exists already
+    let result: RedisResult<()> = con.function_restore(&payload, RestorePolicy::Append);
+    assert!(result.is_err());
+    let () = con
+        .function_restore(&payload, RestorePolicy::Replace)
+        .unwrap();
+    let () = con
+        .function_restore(&payload, RestorePolicy::Flush)
+        .unwrap();
+    let stats: FunctionStats = con.function_stats().unwrap();
+    assert_eq!(stats.engines["LUA"].libraries_count, 1);
+}
+
+#[test]
+fn test_hyperloglog() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.pfadd("hll1", &["a", "b", "c"]), Ok(true));
+    assert_eq!(con.pfadd("hll1", "a"), Ok(false));
+    assert_eq!(con.pfadd("hll2", &["c", "d"]), Ok(true));
+
+    // The estimate is exact for such small cardinalities
+    assert_eq!(con.pfcount("hll1"), Ok(3u64));
+    assert_eq!(con.pfcount(&["hll1", "hll2"]), Ok(4u64));
+    assert_eq!(con.pfcount("missing"), Ok(0u64));
+
+    assert_eq!(con.pfmerge("merged", &["hll1", "hll2"]), Ok(()));
+    assert_eq!(con.pfcount("merged"), Ok(4u64));
+}
+
+#[test]
+fn test_zadd_options() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.zadd("scores", "alice", 10), Ok(1));
+
+    // NX never touches existing members
+    let opts = ZaddOptions::default().nx();
+    assert_eq!(con.zadd_options("scores", "alice", 20, opts), Ok(0));
+    assert_eq!(con.zscore("scores", "alice"), Ok(10));
+
+    // GT only raises scores; CH counts the update
+    let opts = ZaddOptions::default().gt().ch();
+    assert_eq!(con.zadd_options("scores", "alice", 5, opts), Ok(0));
+    let opts = ZaddOptions::default().gt().ch();
+    assert_eq!(con.zadd_options("scores", "alice", 15, opts), Ok(1));
+    assert_eq!(con.zscore("scores", "alice"), Ok(15));
+
+    // LT only lowers scores
+    let opts = ZaddOptions::default().xx().lt();
+    assert_eq!(con.zadd_options("scores", "alice", 12, opts), Ok(0));
+    assert_eq!(con.zscore("scores", "alice"), Ok(12));
+
+    // INCR replies with the new score, or nil when a flag prevented the update
+    let opts = ZaddOptions::default().incr();
+    assert_eq!(
+        con.zadd_options("scores", "alice", 3, opts),
+        Ok(redis::Value::Data(b"15".to_vec()))
+    );
+    let opts = ZaddOptions::default().xx().incr();
+    assert_eq!(
+        con.zadd_options("scores", "bob", 3, opts),
+        Ok(redis::Value::Nil)
+    );
+    assert_eq!(con.zcard("scores"), Ok(1));
+}
+
+#[test]
+fn test_zrange_options() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if con.server_version().unwrap() < (6, 2, 0) {
+        return;
+    }
+
+    let () = con
+        .zadd_multiple(
+            "scoreset",
+            &[(1, "one"), (2, "two"), (3, "three"), (4, "four")],
+        )
+        .unwrap();
+    let () = con
+        .zadd_multiple("lexset", &[(0, "a"), (0, "b"), (0, "c"), (0, "d")])
+        .unwrap();
+
+    let opts = ZRangeOptions::default();
+    let members: Vec<String> = con.zrange_options("scoreset", 0, 1, opts).unwrap();
+    assert_eq!(members, vec!["one", "two"]);
+
+    let opts = ZRangeOptions::default().rev().with_scores();
+    let members: Vec<(String, f64)> = con.zrange_options("scoreset", 0, 1, opts).unwrap();
+    assert_eq!(
+        members,
+        vec![("four".to_string(), 4.0), ("three".to_string(), 3.0)]
+    );
+
+    let opts = ZRangeOptions::default().by_score().limit(1, 2);
+    let members: Vec<String> = con
+        .zrange_options("scoreset", "-inf", "+inf", opts)
+        .unwrap();
+    assert_eq!(members, vec!["two", "three"]);
+
+    let opts = ZRangeOptions::default().by_score().rev().limit(0, 1);
+    let members: Vec<String> = con.zrange_options("scoreset", "(4", "-inf", opts).unwrap();
+    assert_eq!(members, vec!["three"]);
+
+    let opts = ZRangeOptions::default().by_lex().rev();
+    let members: Vec<String> = con.zrange_options("lexset", "[c", "-", opts).unwrap();
+    assert_eq!(members, vec!["c", "b", "a"]);
+}
+
+#[test]
+fn test_zrange_limit() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let () = con
+        .zadd_multiple(
+            "lexset",
+            &[(0, "a"), (0, "b"), (0, "c"), (0, "d"), (0, "e")],
+        )
+        .unwrap();
+    let () = con
+        .zadd_multiple(
+            "scoreset",
+            &[(1, "one"), (2, "two"), (3, "three"), (4, "four")],
+        )
+        .unwrap();
+
+    // Walk through the set two members at a time
+    let page: Vec<String> = con.zrangebylex_limit("lexset", "-", "+", 0, 2).unwrap();
+    assert_eq!(page, vec!["a", "b"]);
+    let page: Vec<String> = con.zrangebylex_limit("lexset", "-", "+", 2, 2).unwrap();
+    assert_eq!(page, vec!["c", "d"]);
+    let page: Vec<String> = con.zrangebylex_limit("lexset", "-", "+", 4, 2).unwrap();
+    assert_eq!(page, vec!["e"]);
+    let page: Vec<String> = con.zrevrangebylex_limit("lexset", "+", "-", 0, 2).unwrap();
+    assert_eq!(page, vec!["e", "d"]);
+
+    let page: Vec<String> = con
+        .zrangebyscore_limit("scoreset", 2, "+inf", 1, 2)
+        .unwrap();
+    assert_eq!(page, vec!["three", "four"]);
+    let page: Vec<(String, f64)> = con
+        .zrangebyscore_limit_withscores("scoreset", "-inf", "+inf", 0, 1)
+        .unwrap();
+    assert_eq!(page, vec![("one".to_string(), 1.0)]);
+}
+
+// Requires redis-server >= 6.2.0.
+// Not supported with the current appveyor/windows binary deployed.
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_zrandmember() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let setname = "myzrandset";
+    let () = con.zadd(setname, "one", 1).unwrap();
+
+    let result: String = con.zrandmember(setname, None).unwrap();
+    assert_eq!(result, "one".to_string());
+
+    let result: Vec<String> = con.zrandmember(setname, Some(1)).unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0], "one".to_string());
+
+    let result: Vec<String> = con.zrandmember(setname, Some(2)).unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0], "one".to_string());
+
+    assert_eq!(
+        con.zadd_multiple(
+            setname,
+            &[(2, "two"), (3, "three"), (4, "four"), (5, "five")]
+        ),
+        Ok(4)
+    );
 
     let results: Vec<String> = con.zrandmember(setname, Some(5)).unwrap();
     assert_eq!(results.len(), 5);
@@ -1107,6 +1822,44 @@ fn test_zrandmember() {
     assert_eq!(results.len(), 10);
 }
 
+#[test]
+fn test_bzmpop() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if con.server_version().unwrap() < (7, 0, 0) {
+        return;
+    }
+
+    let () = con
+        .zadd_multiple("bzmpop_key", &[(1, "one"), (2, "two"), (3, "three")])
+        .unwrap();
+
+    let popped: Option<ZMPopReply> = con
+        .bzmpop(0.1, &["bzmpop_missing", "bzmpop_key"], SortOrder::Max, 2)
+        .unwrap();
+    assert_eq!(
+        popped,
+        Some(ZMPopReply {
+            key: "bzmpop_key".to_string(),
+            members: vec![("three".to_string(), 3.0), ("two".to_string(), 2.0)],
+        })
+    );
+
+    let popped: Option<ZMPopReply> = con.bzmpop(0.1, &["bzmpop_key"], SortOrder::Min, 2).unwrap();
+    assert_eq!(
+        popped,
+        Some(ZMPopReply {
+            key: "bzmpop_key".to_string(),
+            members: vec![("one".to_string(), 1.0)],
+        })
+    );
+
+    // Times out on empty sets
+    let popped: Option<ZMPopReply> = con.bzmpop(0.1, &["bzmpop_key"], SortOrder::Min, 1).unwrap();
+    assert_eq!(popped, None);
+}
+
 #[test]
 fn test_object_commands() {
     let ctx = TestContext::new();
@@ -1125,9 +1878,13 @@ fn test_object_commands() {
         "int"
     );
 
-    assert!(con.object_idletime::<_, i32>("object_key_str").unwrap() <= 1);
+    assert!(con.object_idletime("object_key_str").unwrap().unwrap() <= Duration::from_secs(1));
+    assert_eq!(con.object_idletime("object_key_missing").unwrap(), None);
     assert_eq!(con.object_refcount::<_, i32>("object_key_str").unwrap(), 1);
 
+    let help: Vec<String> = con.object_help().unwrap();
+    assert!(help.iter().any(|line| line.starts_with("ENCODING")));
+
     // Needed for OBJECT FREQ and can't be set before object_idletime
     // since that will break getting the idletime before idletime adjuts
     redis::cmd("CONFIG")
@@ -1173,3 +1930,560 @@ fn test_variable_length_get() {
     let data: Vec<String> = con.get(&keys).unwrap();
     assert_eq!(data, vec!["1"]);
 }
+
+#[test]
+fn test_client_pause() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con
+        .client_pause(100, Some(redis::ClientPauseMode::Write))
+        .unwrap();
+    // reads are still served while only writes are paused
+    let _: Option<String> = con.get("client_pause_key").unwrap();
+    let _: () = con.client_unpause().unwrap();
+
+    let _: () = con.set("client_pause_key", 42).unwrap();
+    assert_eq!(con.get("client_pause_key"), Ok(42));
+}
+
+#[test]
+fn test_client_list() {
+    use redis::{ClientInfo, ClientType};
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+    let _: () = redis::cmd("CLIENT")
+        .arg("SETNAME")
+        .arg("lister")
+        .query(&mut con)
+        .unwrap();
+    let id: u64 = redis::cmd("CLIENT").arg("ID").query(&mut con).unwrap();
+
+    let mut sub_con = ctx.connection();
+    let mut pubsub = sub_con.as_pubsub();
+    pubsub.subscribe("client_list_channel").unwrap();
+
+    let clients: Vec<ClientInfo> = con.client_list_filtered(ClientType::Normal).unwrap();
+    assert!(clients.iter().any(|c| c.id == id && c.name == "lister"));
+
+    let clients: Vec<ClientInfo> = con.client_list_filtered(ClientType::PubSub).unwrap();
+    assert_eq!(clients.len(), 1);
+    assert_ne!(clients[0].id, id);
+
+    let clients: Vec<ClientInfo> = con.client_list_id(&[id]).unwrap();
+    assert_eq!(clients.len(), 1);
+    assert_eq!(clients[0].id, id);
+    assert!(clients[0].cmd.starts_with("client"));
+}
+
+#[test]
+fn test_client_kill() {
+    use redis::ClientInfo;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let mut victim = ctx.connection();
+    let id: u64 = redis::cmd("CLIENT").arg("ID").query(&mut victim).unwrap();
+    assert_eq!(con.client_kill_by_id(id), Ok(true));
+    assert_eq!(con.client_kill_by_id(id), Ok(false));
+
+    let mut victim = ctx.connection();
+    let id: u64 = redis::cmd("CLIENT").arg("ID").query(&mut victim).unwrap();
+    let clients: Vec<ClientInfo> = con.client_list_id(&[id]).unwrap();
+    assert_eq!(con.client_kill_by_addr(&clients[0].addr), Ok(1));
+    assert_eq!(con.client_kill_by_addr(&clients[0].addr), Ok(0));
+}
+
+#[test]
+fn test_client_no_evict() {
+    use redis::ClientInfo;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if con.server_version().unwrap() < (7, 0, 0) {
+        return;
+    }
+
+    let id: u64 = redis::cmd("CLIENT").arg("ID").query(&mut con).unwrap();
+    // `e` is the flag of clients excluded from client eviction
+    let flags = |con: &mut redis::Connection| {
+        let clients: Vec<ClientInfo> = con.client_list_id(&[id]).unwrap();
+        clients[0].flags.clone()
+    };
+
+    let _: () = con.client_no_evict(true).unwrap();
+    assert!(flags(&mut con).contains('e'));
+    let _: () = con.client_no_evict(false).unwrap();
+    assert!(!flags(&mut con).contains('e'));
+}
+
+#[test]
+#[cfg(feature = "test-support")]
+fn test_debug_change_repl_id() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let before: redis::InfoDict = redis::cmd("INFO")
+        .arg("replication")
+        .query(&mut con)
+        .unwrap();
+    let result: redis::RedisResult<()> = con.debug_change_repl_id();
+    match result {
+        // DEBUG is disabled by default from Redis 7.0 on
+        Err(err) if err.to_string().contains("DEBUG command not allowed") => return,
+        result => result.unwrap(),
+    }
+    let after: redis::InfoDict = redis::cmd("INFO")
+        .arg("replication")
+        .query(&mut con)
+        .unwrap();
+
+    let before: String = before.get("master_replid").unwrap();
+    let after: String = after.get("master_replid").unwrap();
+    assert_ne!(before, after);
+}
+
+#[test]
+#[cfg(feature = "test-support")]
+fn test_debug_set_active_expire() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let result: redis::RedisResult<()> = con.debug_set_active_expire(false);
+    match result {
+        // DEBUG is disabled by default from Redis 7.0 on
+        Err(err) if err.to_string().contains("DEBUG command not allowed") => return,
+        result => result.unwrap(),
+    }
+
+    let () = con.pset_ex("foo", 42, 1).unwrap();
+    sleep(Duration::from_millis(200));
+
+    // The expired key is only removed once it is accessed
+    assert_eq!(redis::cmd("DBSIZE").query(&mut con), Ok(1));
+    assert_eq!(con.exists("foo"), Ok(false));
+    assert_eq!(redis::cmd("DBSIZE").query(&mut con), Ok(0));
+
+    let () = con.debug_set_active_expire(true).unwrap();
+}
+
+#[test]
+#[cfg(feature = "test-support")]
+fn test_debug_quicklist_packed_threshold() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if con.server_version().unwrap() < (7, 0, 0) {
+        return;
+    }
+
+    let result: redis::RedisResult<()> = con.debug_quicklist_packed_threshold(100);
+    match result {
+        // DEBUG is disabled by default from Redis 7.0 on
+        Err(err) if err.to_string().contains("DEBUG command not allowed") => return,
+        result => result.unwrap(),
+    }
+
+    // Large elements go to plain nodes, which must read back unchanged
+    let large = "x".repeat(200);
+    let () = con.rpush("list", &["a", large.as_str(), "b"]).unwrap();
+    assert_eq!(
+        con.lrange("list", 0, -1),
+        Ok(vec!["a".to_string(), large, "b".to_string()])
+    );
+
+    let () = con.debug_quicklist_packed_threshold(1 << 30).unwrap();
+}
+
+#[test]
+fn test_command_introspection() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let count: usize = con.command_count().unwrap();
+    assert!(count > 0);
+
+    let info: Vec<Option<redis::CommandInfo>> =
+        con.command_info(&["get", "not-a-command"]).unwrap();
+    assert_eq!(info.len(), 2);
+    let get = info[0].as_ref().unwrap();
+    assert_eq!(get.name, "get");
+    assert_eq!(get.arity, 2);
+    assert!(get.flags.contains(&"readonly".to_string()));
+    assert_eq!((get.first_key, get.last_key, get.step), (1, 1, 1));
+    assert_eq!(info[1], None);
+}
+
+#[test]
+fn test_command_getkeys() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let keys: Vec<String> = con.command_getkeys(&["MSET", "a", "1", "b", "2"]).unwrap();
+    assert_eq!(keys, vec!["a", "b"]);
+}
+
+#[test]
+fn test_wait_for_encoding() {
+    use redis::Encoding;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set("wait_for_encoding_key", 42).unwrap();
+    redis::testing::wait_for_encoding(
+        &mut con,
+        "wait_for_encoding_key",
+        Encoding::Int,
+        Duration::from_millis(100),
+    )
+    .unwrap();
+
+    let err = redis::testing::wait_for_encoding(
+        &mut con,
+        "wait_for_encoding_key",
+        Encoding::Hashtable,
+        Duration::from_millis(50),
+    )
+    .unwrap_err();
+    assert!(err.is_timeout());
+}
+
+#[test]
+fn test_await_encoding_change() {
+    use redis::testing::await_encoding_change;
+    use redis::Encoding;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.sadd("set", &[1, 2, 3]), Ok(3));
+    assert_eq!(con.object_encoding("set"), Ok(Encoding::Intset));
+    // Members that aren't integers convert the set. Redis 7.2 keeps small sets
+    // as listpacks, so add more than `set-max-listpack-entries` (128 by default)
+    // to end up with a hashtable on every version.
+    let members: Vec<String> = (0..200).map(|i| format!("m{i}")).collect();
+    assert_eq!(con.sadd("set", &members), Ok(200));
+    let timeout = Duration::from_millis(100);
+    await_encoding_change(
+        &mut con,
+        "set",
+        Encoding::Intset,
+        Encoding::Hashtable,
+        timeout,
+    )
+    .unwrap();
+
+    let err = await_encoding_change(
+        &mut con,
+        "set",
+        Encoding::Hashtable,
+        Encoding::Intset,
+        timeout,
+    )
+    .unwrap_err();
+    assert!(err.is_timeout());
+
+    let err = await_encoding_change(
+        &mut con,
+        "missing",
+        Encoding::Intset,
+        Encoding::Hashtable,
+        timeout,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), redis::ErrorKind::ClientError);
+}
+
+#[test]
+fn test_command_list() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    if con.server_version().unwrap() < (7, 0, 0) {
+        return;
+    }
+
+    let all: Vec<String> = con.command_list(None).unwrap();
+    assert!(all.iter().any(|name| name == "get"));
+    assert_eq!(Ok(all.len()), con.command_count());
+
+    // Later versions or modules may add commands matching the pattern
+    let matching: Vec<String> = con
+        .command_list(Some(CommandListFilter::Pattern("xa*".to_string())))
+        .unwrap();
+    for name in ["xack", "xadd", "xautoclaim"] {
+        assert!(matching.iter().any(|m| m == name));
+    }
+    assert!(matching.iter().all(|name| name.starts_with("xa")));
+
+    let dangerous: Vec<String> = con
+        .command_list(Some(CommandListFilter::AclCat("dangerous".to_string())))
+        .unwrap();
+    assert!(dangerous.iter().any(|name| name == "flushall"));
+    assert!(!dangerous.iter().any(|name| name == "get"));
+
+    let from_module: Vec<String> = con
+        .command_list(Some(CommandListFilter::Module("missing".to_string())))
+        .unwrap();
+    assert!(from_module.is_empty());
+}
+
+#[test]
+fn test_type_of() {
+    use redis::KeyType;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set("string", 1).unwrap();
+    let _: () = con.rpush("list", 1).unwrap();
+    let _: () = con.sadd("set", 1).unwrap();
+    let _: () = con.zadd("zset", 1, 1).unwrap();
+    let _: () = con.hset("hash", "field", 1).unwrap();
+
+    assert_eq!(con.type_of("string"), Ok(Some(KeyType::String)));
+    assert_eq!(con.type_of("list"), Ok(Some(KeyType::List)));
+    assert_eq!(con.type_of("set"), Ok(Some(KeyType::Set)));
+    assert_eq!(con.type_of("zset"), Ok(Some(KeyType::ZSet)));
+    assert_eq!(con.type_of("hash"), Ok(Some(KeyType::Hash)));
+    assert_eq!(con.type_of("missing"), Ok(None));
+}
+
+#[test]
+fn test_multi_get_with_types() {
+    use redis::{KeyType, Value};
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set("string", "value").unwrap();
+    let _: () = con.rpush("list", 1).unwrap();
+
+    assert_eq!(
+        con.multi_get_with_types(&["string", "list", "missing"]),
+        Ok(vec![
+            (Some(Value::Data(b"value".to_vec())), Some(KeyType::String)),
+            (None, Some(KeyType::List)),
+            (None, None),
+        ])
+    );
+
+    let empty: [&str; 0] = [];
+    assert_eq!(con.multi_get_with_types(&empty), Ok(vec![]));
+}
+
+#[test]
+fn test_object_encoding_or_type() {
+    use redis::{Encoding, EncodingOrType, KeyType};
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set("string", 1).unwrap();
+    assert_eq!(
+        con.object_encoding_or_type("string"),
+        Ok(Some(EncodingOrType::Encoding(Encoding::Int)))
+    );
+    assert_eq!(con.object_encoding_or_type("missing"), Ok(None));
+
+    // A user that isn't allowed to run OBJECT gets the type instead
+    let _: () = redis::cmd("ACL")
+        .arg(&[
+            "SETUSER", "noobject", "on", "nopass", "~*", "+@all", "-object",
+        ])
+        .query(&mut con)
+        .unwrap();
+    let mut restricted = ctx.connection();
+    let _: () = redis::cmd("AUTH")
+        .arg(&["noobject", "nopass"])
+        .query(&mut restricted)
+        .unwrap();
+    assert_eq!(
+        restricted.object_encoding_or_type("string"),
+        Ok(Some(EncodingOrType::Type(KeyType::String)))
+    );
+    assert_eq!(restricted.object_encoding_or_type("missing"), Ok(None));
+}
+
+#[test]
+fn test_object_encoding_all() {
+    use redis::Encoding;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let mut keys = Vec::new();
+    let mut expected = Vec::new();
+    for i in 0..100 {
+        let key = format!("encoding_all_{i}");
+        match i % 4 {
+            0 => {
+                let _: () = con.set(&key, i).unwrap();
+                expected.push(Encoding::Int);
+            }
+            1 => {
+                let _: () = con.set(&key, "short").unwrap();
+                expected.push(Encoding::Embstr);
+            }
+            2 => {
+                let _: () = con.set(&key, "x".repeat(100)).unwrap();
+                expected.push(Encoding::Raw);
+            }
+            _ => {
+                let _: () = con.sadd(&key, &[1, 2, 3]).unwrap();
+                expected.push(Encoding::Intset);
+            }
+        }
+        keys.push(key);
+    }
+
+    assert_eq!(con.object_encoding_all(&keys), Ok(expected));
+    assert_eq!(con.object_encoding_all::<&str>(&[]), Ok(vec![]));
+}
+
+#[test]
+fn test_encoding_table() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set("counter", 42).unwrap();
+    let _: () = con.sadd("members", &[1, 2, 3]).unwrap();
+
+    let table =
+        redis::diagnostics::encoding_table(&mut con, &["counter", "members", "missing"]).unwrap();
+    let rows: Vec<Vec<&str>> = table
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0], vec!["KEY", "TYPE", "ENCODING", "MEMORY"]);
+    assert_eq!(rows[1][..3], ["counter", "string", "int"]);
+    assert!(rows[1][3].parse::<u64>().unwrap() > 0);
+    assert_eq!(rows[2][..3], ["members", "set", "intset"]);
+    assert_eq!(rows[3], vec!["missing", "none", "-", "-"]);
+
+    // The columns are aligned
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+}
+
+#[test]
+fn test_dbsize_for_all_databases() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let _: () = con.set_multiple(&[("a", 1), ("b", 2)]).unwrap();
+    con.select_db(2).unwrap();
+    let _: () = con.set("c", 3).unwrap();
+    assert_eq!(con.dbsize(), Ok(1));
+
+    assert_eq!(
+        redis::diagnostics::dbsize_for_all_databases(&mut con),
+        Ok(HashMap::from([(0, 2), (2, 1)]))
+    );
+
+    // The connection is back on the database it was using
+    assert_eq!(con.get_db(), 2);
+    assert_eq!(con.get("c"), Ok(3));
+}
+
+#[test]
+fn test_object_encoding_sorted_set() {
+    use redis::Encoding;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    // Sorted sets use the compact encoding up to `zset-max-listpack-entries`
+    // members, 128 by default.
+    let small: Vec<(u32, u32)> = (0..10).map(|i| (i, i)).collect();
+    let large: Vec<(u32, u32)> = (0..200).map(|i| (i, i)).collect();
+    let _: () = con.zadd_multiple("small", &small).unwrap();
+    let _: () = con.zadd_multiple("large", &large).unwrap();
+
+    let compact = if con.server_version().unwrap() < (7, 0, 0) {
+        Encoding::Ziplist
+    } else {
+        Encoding::Listpack
+    };
+    assert_eq!(con.object_encoding("small"), Ok(compact));
+    assert_eq!(con.object_encoding("large"), Ok(Encoding::Skiplist));
+}
+
+#[test]
+fn test_object_encoding_in_transaction() {
+    use redis::Encoding;
+
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    // The encodings are read from the EXEC reply, once the keys are written
+    let (int, set, missing): (Encoding, Encoding, Option<Encoding>) = redis::pipe()
+        .atomic()
+        .set("tx_int", 42)
+        .ignore()
+        .object_encoding("tx_int")
+        .sadd("tx_set", &[1, 2, 3])
+        .ignore()
+        .object_encoding("tx_set")
+        .object_encoding("tx_missing")
+        .query(&mut con)
+        .unwrap();
+    assert_eq!(int, Encoding::Int);
+    assert_eq!(set, Encoding::Intset);
+    assert_eq!(missing, None);
+}
+
+#[test]
+fn test_resp3() {
+    use redis::ProtocolVersion;
+
+    let ctx = TestContext::new();
+    let mut info = ctx.server.connection_info();
+    info.redis.use_resp3 = true;
+    let client = redis::Client::open(info).unwrap();
+    let mut con = client.get_connection().unwrap();
+    assert_eq!(con.get_protocol(), ProtocolVersion::RESP3);
+    assert_eq!(ctx.connection().get_protocol(), ProtocolVersion::RESP2);
+
+    let _: () = con
+        .hset_multiple("resp3_hash", &[("a", 1), ("b", 2)])
+        .unwrap();
+    let value: redis::Value = redis::cmd("HGETALL")
+        .arg("resp3_hash")
+        .query(&mut con)
+        .unwrap();
+    assert!(matches!(value, redis::Value::Map(_)));
+
+    let map: HashMap<String, i64> = con.hgetall("resp3_hash").unwrap();
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(map.get("b"), Some(&2));
+}
+
+#[test]
+#[cfg(feature = "r2d2")]
+fn test_redis_pool() {
+    use redis::RedisPool;
+
+    fn incr<P: RedisPool<redis::Connection>>(pool: &P) -> RedisResult<u64> {
+        let mut con = pool.get()?;
+        let count = con.incr("pooled", 1)?;
+        pool.put(con);
+        Ok(count)
+    }
+
+    let ctx = TestContext::new();
+    let pool = r2d2::Pool::builder()
+        .max_size(1)
+        .build(ctx.client.clone())
+        .unwrap();
+
+    assert_eq!(incr(&pool), Ok(1));
+    // The only connection of the pool was put back, so it can be checked out again
+    assert_eq!(incr(&pool), Ok(2));
+    assert_eq!(pool.state().idle_connections, 1);
+}
@@ -2,6 +2,7 @@
 mod support;
 use crate::support::*;
 use redis::cluster::cluster_pipe;
+use redis::Commands;
 
 #[test]
 fn test_cluster_basics() {
@@ -22,6 +23,332 @@ fn test_cluster_basics() {
     );
 }
 
+#[test]
+fn test_cluster_keys_in_slot() {
+    let cluster = TestClusterContext::new(3, 0);
+    let mut con = cluster.connection();
+
+    redis::cmd("SET").arg("{x}key1").arg(1).execute(&mut con);
+    redis::cmd("SET").arg("{x}key2").arg(2).execute(&mut con);
+
+    let mut total = 0;
+    let mut keys = Vec::new();
+    for server in cluster.cluster.iter_servers() {
+        let client = redis::Client::open(server.connection_info()).unwrap();
+        let mut node = client.get_connection().unwrap();
+        let slot: u16 = redis::cmd("CLUSTER")
+            .arg("KEYSLOT")
+            .arg("{x}key1")
+            .query(&mut node)
+            .unwrap();
+        let count: u64 = node.cluster_countkeysinslot(slot).unwrap();
+        total += count;
+        let found: Vec<String> = node.cluster_getkeysinslot(slot, 10).unwrap();
+        keys.extend(found);
+    }
+    keys.sort();
+
+    assert_eq!(total, 2);
+    assert_eq!(keys, vec!["{x}key1", "{x}key2"]);
+}
+
+#[test]
+fn test_cluster_setslot() {
+    use redis::cluster::key_hash_slot;
+    use redis::ClusterSlot;
+
+    let cluster = TestClusterContext::new(3, 0);
+    let slot = key_hash_slot(b"{migrated}");
+
+    let mut nodes = Vec::new();
+    for server in cluster.cluster.iter_servers() {
+        let client = redis::Client::open(server.connection_info()).unwrap();
+        let mut node = client.get_connection().unwrap();
+        let id: String = redis::cmd("CLUSTER").arg("MYID").query(&mut node).unwrap();
+        nodes.push((id, node));
+    }
+    let slots: Vec<ClusterSlot> = nodes[0].1.cluster_slots().unwrap();
+    let owner = slots
+        .iter()
+        .find(|range| range.start <= slot && slot <= range.end)
+        .and_then(|range| range.primary.id.clone())
+        .unwrap();
+    let source = nodes.iter().position(|(id, _)| *id == owner).unwrap();
+    let target = (source + 1) % nodes.len();
+    let source_id = nodes[source].0.clone();
+    let target_id = nodes[target].0.clone();
+
+    // An aborted migration leaves the slot where it was.
+    let _: () = nodes[target]
+        .1
+        .cluster_setslot_importing(slot, &source_id)
+        .unwrap();
+    let _: () = nodes[target].1.cluster_setslot_stable(slot).unwrap();
+
+    // The slot is empty, so there are no keys to move before reassigning it.
+    let _: () = nodes[target]
+        .1
+        .cluster_setslot_importing(slot, &source_id)
+        .unwrap();
+    let _: () = nodes[source]
+        .1
+        .cluster_setslot_migrating(slot, &target_id)
+        .unwrap();
+    let _: () = nodes[target]
+        .1
+        .cluster_setslot_node(slot, &target_id)
+        .unwrap();
+    let _: () = nodes[source]
+        .1
+        .cluster_setslot_node(slot, &target_id)
+        .unwrap();
+
+    let err = redis::cmd("GET")
+        .arg("{migrated}")
+        .query::<Option<String>>(&mut nodes[source].1)
+        .unwrap_err();
+    assert_eq!(err.kind(), redis::ErrorKind::Moved);
+    let value: Option<String> = nodes[target].1.get("{migrated}").unwrap();
+    assert_eq!(value, None);
+}
+
+#[test]
+fn test_cluster_get_primary_for_key() {
+    let cluster = TestClusterContext::new(3, 0);
+    let mut con = cluster.connection();
+
+    for i in 0..10 {
+        let key = format!("primary{i}");
+        let _: () = con.set(&key, i).unwrap();
+
+        // The primary serves the key without redirecting.
+        let info = con.get_primary_for_key(&key).unwrap();
+        let client = redis::Client::open(info).unwrap();
+        let mut node = client.get_connection().unwrap();
+        assert_eq!(node.get(&key), Ok(i));
+    }
+
+    let err = con.get_primary_for_key(&["a", "b"]).unwrap_err();
+    assert_eq!(err.kind(), redis::ErrorKind::ClientError);
+}
+
+#[test]
+fn test_cluster_request_router() {
+    use redis::cluster::{ClusterClientBuilder, RequestRouter};
+    use std::sync::Arc;
+
+    // Sends the commands mentioning "left" to the first node, and the others to the second.
+    struct LeftRight;
+
+    impl RequestRouter for LeftRight {
+        fn route(&self, cmd: &[u8]) -> Option<usize> {
+            if cmd.windows(4).any(|w| w == b"left") {
+                Some(0)
+            } else {
+                Some(1)
+            }
+        }
+    }
+
+    // Standalone servers stand in for proxies: they don't support `CLUSTER SLOTS`.
+    let left = TestContext::new();
+    let right = TestContext::new();
+    let client = ClusterClientBuilder::new(vec![
+        left.server.connection_info(),
+        right.server.connection_info(),
+    ])
+    .with_request_router(Arc::new(LeftRight))
+    .build()
+    .unwrap();
+    let mut con = client.get_connection().unwrap();
+
+    let () = con.set("left", 1).unwrap();
+    let () = con.set("right", 2).unwrap();
+    let () = cluster_pipe()
+        .set("left2", 3)
+        .set("right2", 4)
+        .query(&mut con)
+        .unwrap();
+    assert_eq!(con.get("left"), Ok(1));
+    assert_eq!(con.get("right2"), Ok(4));
+
+    let mut keys: Vec<String> = left.connection().keys("*").unwrap();
+    keys.sort();
+    assert_eq!(keys, vec!["left", "left2"]);
+    let mut keys: Vec<String> = right.connection().keys("*").unwrap();
+    keys.sort();
+    assert_eq!(keys, vec!["right", "right2"]);
+}
+
+#[test]
+fn test_cluster_keyslot() {
+    let cluster = TestClusterContext::new(3, 0);
+    let mut con = cluster.connection();
+
+    for key in ["foo", "{foo}bar", "{}foo", "user:{1000}:name", ""] {
+        let slot: u16 = con.cluster_keyslot(key).unwrap();
+        assert_eq!(slot, redis::cluster::key_hash_slot(key.as_bytes()));
+    }
+}
+
+#[test]
+fn test_cluster_slots() {
+    let cluster = TestClusterContext::new(3, 1);
+    let mut con = cluster.connection();
+
+    let mut slots: Vec<redis::ClusterSlot> = con.cluster_slots().unwrap();
+    slots.sort_by_key(|slot| slot.start);
+
+    assert_eq!(slots.len(), 3);
+    assert_eq!(slots[0].start, 0);
+    assert_eq!(slots[2].end, 16383);
+    for slot in slots {
+        assert!(slot.primary.id.is_some());
+        assert_eq!(slot.replicas.len(), 1);
+        assert_ne!(slot.primary, slot.replicas[0]);
+    }
+}
+
+#[test]
+fn test_cluster_object_commands() {
+    let cluster = TestClusterContext::new(3, 0);
+    let mut con = cluster.connection();
+
+    let _: () = con.set("encoded", "value").unwrap();
+
+    // every node but the one owning the key redirects
+    let mut moved = 0;
+    for server in cluster.cluster.iter_servers() {
+        let client = redis::Client::open(server.connection_info()).unwrap();
+        let mut node = client.get_connection().unwrap();
+        let result: redis::RedisResult<redis::Encoding> = node.object_encoding("encoded");
+        match result {
+            Ok(encoding) => assert_eq!(encoding, redis::Encoding::Embstr),
+            Err(err) => {
+                assert_eq!(err.kind(), redis::ErrorKind::Moved);
+                moved += 1;
+            }
+        }
+    }
+    assert_eq!(moved, 2);
+
+    assert_eq!(con.object_encoding("encoded"), Ok(redis::Encoding::Embstr));
+    assert_eq!(con.object_refcount("encoded"), Ok(1));
+    let help: Vec<String> = redis::cmd("OBJECT").arg("HELP").query(&mut con).unwrap();
+    assert!(!help.is_empty());
+}
+
+#[test]
+fn test_cluster_object_encoding_moved() {
+    use redis::cluster::key_hash_slot;
+
+    let cluster = TestClusterContext::new(3, 0);
+    let mut con = cluster.connection();
+
+    // Load the slot map before the slot of the key changes hands.
+    let source_info = con.get_primary_for_key("{stale}").unwrap();
+
+    let slot = key_hash_slot(b"{stale}");
+    let mut nodes = Vec::new();
+    for server in cluster.cluster.iter_servers() {
+        let info = server.connection_info();
+        let client = redis::Client::open(info.clone()).unwrap();
+        let mut node = client.get_connection().unwrap();
+        let id: String = redis::cmd("CLUSTER").arg("MYID").query(&mut node).unwrap();
+        nodes.push((info.addr, id, node));
+    }
+    let source = nodes
+        .iter()
+        .position(|(addr, _, _)| *addr == source_info.addr)
+        .unwrap();
+    let target = (source + 1) % nodes.len();
+    let target_id = nodes[target].1.clone();
+
+    // The slot is empty, so it can be reassigned without moving keys.
+    for (_, _, node) in nodes.iter_mut() {
+        let _: () = node.cluster_setslot_node(slot, &target_id).unwrap();
+    }
+    let _: () = nodes[target].2.set("{stale}", "value").unwrap();
+
+    // The connection still maps the slot to the source, which redirects.
+    let result: redis::RedisResult<redis::Encoding> = nodes[source].2.object_encoding("{stale}");
+    assert_eq!(result.unwrap_err().kind(), redis::ErrorKind::Moved);
+    assert_eq!(con.object_encoding("{stale}"), Ok(redis::Encoding::Embstr));
+
+    // Following the redirection refreshed the slot map.
+    let target_info = con.get_primary_for_key("{stale}").unwrap();
+    assert_eq!(target_info.addr, nodes[target].0);
+}
+
+#[test]
+#[cfg(feature = "cluster-diagnostics")]
+fn test_cluster_diagnostics() {
+    use once_cell::sync::Lazy;
+    use std::sync::Mutex;
+
+    // Collects the messages logged by the cluster connection.
+    struct Collector(Mutex<Vec<String>>);
+
+    impl log::Log for Collector {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target().starts_with("redis::cluster")
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static COLLECTOR: Lazy<Collector> = Lazy::new(|| Collector(Mutex::new(Vec::new())));
+    log::set_logger(&*COLLECTOR).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let cluster = TestClusterContext::new_with_cluster_client_builder(3, 0, |builder| {
+        builder.diagnostics(true).diagnostics_sample_rate(1)
+    });
+    let mut con = cluster.connection();
+
+    let _: () = con.set("diagnosed", 42).unwrap();
+    let _: () = con.del("diagnosed").unwrap();
+
+    let messages = COLLECTOR.0.lock().unwrap();
+    assert!(messages.contains(&r#"key "diagnosed" has encoding int"#.to_string()));
+    assert!(messages.contains(&r#"key "diagnosed" does not exist"#.to_string()));
+}
+
+#[test]
+#[cfg(feature = "tls")]
+fn test_cluster_reload_tls_identity() {
+    let cluster = TestClusterContext::new(3, 0);
+    let identity = match cluster.cluster.tls_paths {
+        Some(ref tls_paths) => tls_paths.client_identity(),
+        // Client certificates only matter to TLS nodes
+        None => return,
+    };
+
+    // Established connections are kept when client certificates become required
+    for server in cluster.cluster.iter_servers() {
+        let client = redis::Client::open(server.connection_info()).unwrap();
+        let mut node = client.get_connection().unwrap();
+        let _: () = redis::cmd("CONFIG")
+            .arg("SET")
+            .arg("tls-auth-clients")
+            .arg("yes")
+            .query(&mut node)
+            .unwrap();
+    }
+    assert!(cluster.client.get_connection().is_err());
+
+    cluster.client.reload_tls_identity(identity).unwrap();
+    let mut con = cluster.connection();
+    let _: () = con.set("rotated", 42).unwrap();
+    assert_eq!(con.get("rotated"), Ok(42));
+}
+
 #[test]
 fn test_cluster_with_username_and_password() {
     let cluster = TestClusterContext::new_with_cluster_client_builder(3, 0, |builder| {
@@ -47,6 +374,55 @@ fn test_cluster_with_username_and_password() {
     );
 }
 
+#[test]
+fn test_cluster_connection_to_node() {
+    let cluster = TestClusterContext::new_with_cluster_client_builder(3, 0, |builder| {
+        builder
+            .username(RedisCluster::username().to_string())
+            .password(RedisCluster::password().to_string())
+    });
+    cluster.disable_default_user();
+
+    let mut ids = std::collections::HashSet::new();
+    for server in cluster.cluster.iter_servers() {
+        let (host, port) = match server.client_addr() {
+            redis::ConnectionAddr::Tcp(host, port) => (host, *port),
+            redis::ConnectionAddr::TcpTls { host, port, .. } => (host, *port),
+            _ => panic!("cluster nodes listen on TCP"),
+        };
+        let mut node = cluster.client.get_connection_to_node(host, port).unwrap();
+        let id: String = redis::cmd("CLUSTER").arg("MYID").query(&mut node).unwrap();
+        ids.insert(id);
+    }
+
+    assert_eq!(ids.len(), 3);
+}
+
+#[test]
+fn test_cluster_fan_out() {
+    let cluster = TestClusterContext::new(3, 0);
+    let mut con = cluster.connection();
+
+    for i in 0..10 {
+        let () = con.set(format!("key{i}"), i).unwrap();
+    }
+
+    let sizes = con.fan_out(|| redis::cmd("DBSIZE")).unwrap();
+    assert_eq!(sizes.len(), 3);
+    let total: i64 = sizes
+        .iter()
+        .map(|(_, size)| redis::from_redis_value::<i64>(size).unwrap())
+        .sum();
+    assert_eq!(total, 10);
+
+    let flushed = con.fan_out(|| redis::cmd("FLUSHDB")).unwrap();
+    assert!(flushed
+        .iter()
+        .all(|(_, reply)| *reply == redis::Value::Okay));
+    let sizes = con.fan_out(|| redis::cmd("DBSIZE")).unwrap();
+    assert!(sizes.iter().all(|(_, size)| *size == redis::Value::Int(0)));
+}
+
 #[test]
 fn test_cluster_with_bad_password() {
     let cluster = TestClusterContext::new_with_cluster_client_builder(3, 0, |builder| {
@@ -80,6 +456,40 @@ fn test_cluster_read_from_replicas() {
     );
 }
 
+#[test]
+fn test_cluster_read_preference_fn() {
+    use redis::cluster::ReadPreference;
+    use std::sync::{Arc, Mutex};
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorder = seen.clone();
+    let cluster = TestClusterContext::new_with_cluster_client_builder(6, 1, move |builder| {
+        builder.read_preference_fn(Arc::new(move |cmd: &[u8]| {
+            recorder.lock().unwrap().push(cmd.to_vec());
+            if cmd.windows(3).any(|w| w == b"GET") {
+                ReadPreference::Primary
+            } else {
+                ReadPreference::Replica
+            }
+        }))
+    });
+    let mut con = cluster.connection();
+
+    let _: () = con.set("{x}key1", "foo").unwrap();
+    // Served by the primary, so the write is visible right away.
+    assert_eq!(con.get("{x}key1"), Ok("foo".to_string()));
+    let _: bool = con.exists("{x}key1").unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(
+        *seen,
+        vec![
+            redis::cmd("GET").arg("{x}key1").get_packed_command(),
+            redis::cmd("EXISTS").arg("{x}key1").get_packed_command(),
+        ]
+    );
+}
+
 #[test]
 fn test_cluster_eval() {
     let cluster = TestClusterContext::new(3, 0);
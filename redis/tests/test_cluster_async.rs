@@ -43,6 +43,27 @@ fn test_async_cluster_basic_cmd() {
     .unwrap();
 }
 
+#[test]
+fn test_async_cluster_object_encoding() {
+    use redis::Encoding;
+
+    let cluster = TestClusterContext::new(3, 0);
+
+    block_on_all(async move {
+        let mut connection = cluster.async_connection().await;
+        // Keys spread over the slots of all the nodes
+        for i in 0..10 {
+            let () = connection.set(format!("int{i}"), i).await?;
+        }
+        for i in 0..10 {
+            let encoding: Encoding = connection.object_encoding(format!("int{i}")).await?;
+            assert_eq!(encoding, Encoding::Int);
+        }
+        Ok::<_, RedisError>(())
+    })
+    .unwrap();
+}
+
 #[test]
 fn test_async_cluster_basic_eval() {
     let cluster = TestClusterContext::new(3, 0);
@@ -195,3 +195,36 @@ fn test_georadius_by_member() {
 
     assert_eq!(names, vec!["Agrigento", "Palermo"]);
 }
+
+#[test]
+fn test_georadius_by_member_with_options() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    assert_eq!(con.geo_add("my_gis", &[PALERMO, CATANIA, AGRIGENTO]), Ok(3));
+
+    let opts = RadiusOptions::default()
+        .with_coord()
+        .with_dist()
+        .with_hash()
+        .order(RadiusOrder::Desc)
+        .limit(2);
+    let result: Vec<RadiusSearchResult> = con
+        .geo_radius_by_member("my_gis", AGRIGENTO.2, 200.0, Unit::Kilometers, opts)
+        .unwrap();
+
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].name.as_str(), "Catania");
+    assert_approx_eq!(result[0].dist.unwrap(), 134.4697, 0.001);
+    assert!(result[0].hash.is_some());
+    assert_approx_eq!(result[0].coord.as_ref().unwrap().longitude, 15.087_269);
+    assert_eq!(result[1].name.as_str(), "Palermo");
+
+    // With STORE, the results are written to a sorted set instead.
+    let opts = RadiusOptions::default().store("nearby");
+    let stored: usize = con
+        .geo_radius_by_member("my_gis", AGRIGENTO.2, 100.0, Unit::Kilometers, opts)
+        .unwrap();
+    assert_eq!(stored, 2);
+    assert_eq!(con.zcard("nearby"), Ok(2));
+}
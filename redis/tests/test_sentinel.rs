@@ -0,0 +1,132 @@
+#![allow(clippy::let_unit_value)]
+
+use redis::{Commands, ConnectionLike, ErrorKind, RedisResult, Value};
+
+// A connection expecting a single command, which it answers with `reply`.
+struct MockSentinel {
+    expected: Vec<u8>,
+    reply: Option<RedisResult<Value>>,
+}
+
+impl MockSentinel {
+    fn new(expected: &[u8], reply: RedisResult<Value>) -> MockSentinel {
+        MockSentinel {
+            expected: expected.to_vec(),
+            reply: Some(reply),
+        }
+    }
+}
+
+impl ConnectionLike for MockSentinel {
+    fn req_packed_command(&mut self, cmd: &[u8]) -> RedisResult<Value> {
+        assert_eq!(
+            String::from_utf8_lossy(cmd),
+            String::from_utf8_lossy(&self.expected)
+        );
+        self.reply.take().expect("Sent more than one command")
+    }
+
+    fn req_packed_commands(
+        &mut self,
+        _cmd: &[u8],
+        _offset: usize,
+        _count: usize,
+    ) -> RedisResult<Vec<Value>> {
+        Err((
+            ErrorKind::ClientError,
+            "pipelines are not supported by the mock",
+        )
+            .into())
+    }
+
+    fn get_db(&self) -> i64 {
+        0
+    }
+
+    fn check_connection(&mut self) -> bool {
+        true
+    }
+
+    fn is_open(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_sentinel_get_master_addr_by_name() {
+    let packed = b"*3\r\n$8\r\nSENTINEL\r\n$23\r\nGET-MASTER-ADDR-BY-NAME\r\n$8\r\nmymaster\r\n";
+    assert_eq!(
+        redis::Cmd::sentinel_get_master_addr_by_name("mymaster").get_packed_command(),
+        packed
+    );
+
+    let mut con = MockSentinel::new(
+        packed,
+        Ok(Value::Bulk(vec![
+            Value::Data("127.0.0.1".into()),
+            Value::Data("6379".into()),
+        ])),
+    );
+    let addr: Option<(String, u16)> = con.sentinel_get_master_addr_by_name("mymaster").unwrap();
+    assert_eq!(addr, Some(("127.0.0.1".to_string(), 6379)));
+
+    // Unknown masters
+    let mut con = MockSentinel::new(packed, Ok(Value::Nil));
+    let addr: Option<(String, u16)> = con.sentinel_get_master_addr_by_name("mymaster").unwrap();
+    assert_eq!(addr, None);
+}
+
+#[test]
+fn test_sentinel_failover() {
+    let packed = b"*3\r\n$8\r\nSENTINEL\r\n$8\r\nFAILOVER\r\n$8\r\nmymaster\r\n";
+    assert_eq!(
+        redis::Cmd::sentinel_failover("mymaster").get_packed_command(),
+        packed
+    );
+
+    let mut con = MockSentinel::new(packed, Ok(Value::Okay));
+    let () = con.sentinel_failover("mymaster").unwrap();
+}
+
+#[test]
+fn test_sentinel_reset() {
+    let packed = b"*3\r\n$8\r\nSENTINEL\r\n$5\r\nRESET\r\n$3\r\nmy*\r\n";
+    assert_eq!(
+        redis::Cmd::sentinel_reset("my*").get_packed_command(),
+        packed
+    );
+
+    let mut con = MockSentinel::new(packed, Ok(Value::Int(2)));
+    let reset: usize = con.sentinel_reset("my*").unwrap();
+    assert_eq!(reset, 2);
+}
+
+#[test]
+fn test_sentinel_ckquorum() {
+    let packed = b"*3\r\n$8\r\nSENTINEL\r\n$8\r\nCKQUORUM\r\n$8\r\nmymaster\r\n";
+    assert_eq!(
+        redis::Cmd::sentinel_ckquorum("mymaster").get_packed_command(),
+        packed
+    );
+
+    let mut con = MockSentinel::new(
+        packed,
+        redis::parse_redis_value(
+            b"+OK 3 usable Sentinels. Quorum and failover authorization can be reached\r\n",
+        ),
+    );
+    let () = con.sentinel_ckquorum("mymaster").unwrap();
+
+    let mut con = MockSentinel::new(
+        packed,
+        redis::parse_redis_value(
+            b"-NOQUORUM 1 usable Sentinels. Not enough available Sentinels to reach the specified quorum for this master\r\n",
+        ),
+    );
+    let err = con.sentinel_ckquorum::<()>("mymaster").unwrap_err();
+    assert_eq!(err.code(), Some("NOQUORUM"));
+    assert_eq!(
+        err.detail(),
+        Some("1 usable Sentinels. Not enough available Sentinels to reach the specified quorum for this master")
+    );
+}
@@ -204,6 +204,35 @@ fn test_xgroup_create() {
     assert_eq!(&reply.groups[0].name, &"g1");
 }
 
+#[test]
+fn test_xread_multiple() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    xadd(&mut con);
+
+    let streams = con
+        .xread_multiple(None, &["k1", "k2", "k3"], &["0", "0", "0"])
+        .unwrap();
+    // k3 doesn't exist, so it is left out
+    assert_eq!(streams.len(), 2);
+    let ids: Vec<&str> = streams["k1"].ids.iter().map(|id| id.id.as_str()).collect();
+    assert_eq!(ids, vec!["1000-0", "1000-1"]);
+    let ids: Vec<&str> = streams["k2"].ids.iter().map(|id| id.id.as_str()).collect();
+    assert_eq!(ids, vec!["2000-0", "2000-1"]);
+
+    let streams = con
+        .xread_multiple(Some(1), &["k1", "k2"], &["1000-0", "0"])
+        .unwrap();
+    assert_eq!(streams["k1"].ids.len(), 1);
+    assert_eq!(streams["k1"].ids[0].id, "1000-1");
+    assert_eq!(streams["k2"].ids.len(), 1);
+    assert_eq!(streams["k2"].ids[0].id, "2000-0");
+
+    let streams = con.xread_multiple(None, &["k1"], &["$"]).unwrap();
+    assert!(streams.is_empty());
+}
+
 #[test]
 fn test_assorted_2() {
     // Tests the following commands....
@@ -397,6 +426,30 @@ fn test_xread_options_deleted_pel_entry() {
         result_deleted_entry.keys[0].ids[0].id
     );
 }
+#[test]
+fn test_xread_group_noack() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    xadd(&mut con);
+    let result: RedisResult<String> = con.xgroup_create("k1", "g1", "0");
+    assert!(result.is_ok());
+
+    let reply: Option<StreamReadReply> =
+        con.xread_group_noack("g1", "c1", &["k1"], &[">"]).unwrap();
+    let reply = reply.unwrap();
+    assert_eq!(reply.keys[0].ids.len(), 2);
+
+    // nothing was added to the PEL, so there is nothing to acknowledge
+    let pending: StreamPendingReply = con.xpending("k1", "g1").unwrap();
+    assert_eq!(pending.count(), 0);
+
+    // everything has been delivered already
+    let reply: Option<StreamReadReply> =
+        con.xread_group_noack("g1", "c1", &["k1"], &[">"]).unwrap();
+    assert!(reply.is_none());
+}
+
 #[test]
 fn test_xclaim() {
     // Tests the following commands....
@@ -502,6 +555,36 @@ fn test_xclaim() {
     assert_eq!(claimed.len(), 10);
 }
 
+#[test]
+fn test_xack_multiple() {
+    // Tests the following commands....
+    // xack
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    xadd_keyrange(&mut con, "k1", 0, 3);
+    let _: String = con.xgroup_create("k1", "g1", "0").unwrap();
+
+    let reply: StreamReadReply = con
+        .xread_options(
+            &["k1"],
+            &[">"],
+            &StreamReadOptions::default().group("g1", "c1"),
+        )
+        .unwrap();
+    let ids: Vec<&str> = reply.keys[0].ids.iter().map(|id| id.id.as_str()).collect();
+    assert_eq!(ids.len(), 3);
+
+    // only ids pending in the group are counted
+    let result: RedisResult<usize> = con.xack("k1", "g1", &[ids[0], ids[1], "9999-0"]);
+    assert_eq!(result, Ok(2));
+    let result: RedisResult<usize> = con.xack("k1", "g1", &[ids[0]]);
+    assert_eq!(result, Ok(0));
+
+    let reply: StreamPendingReply = con.xpending("k1", "g1").unwrap();
+    assert_eq!(reply.count(), 1);
+}
+
 #[test]
 fn test_xdel() {
     // Tests the following commands....
@@ -546,6 +629,7 @@ fn test_xgroup() {
     // Tests the following commands....
     // xgroup_create_mkstream
     // xgroup_destroy
+    // xgroup_createconsumer
     // xgroup_delconsumer
 
     let ctx = TestContext::new();
@@ -566,6 +650,12 @@ fn test_xgroup() {
     let result: RedisResult<String> = con.xgroup_create("k1", "g1", "0");
     assert!(result.is_ok());
 
+    // explicitly create a consumer, then make sure it isn't created twice
+    let result: RedisResult<bool> = con.xgroup_createconsumer("k1", "g1", "c2");
+    assert_eq!(result, Ok(true));
+    let result: RedisResult<bool> = con.xgroup_createconsumer("k1", "g1", "c2");
+    assert_eq!(result, Ok(false));
+
     // read from the group so we can register the consumer
     let reply: StreamReadReply = con
         .xread_options(
@@ -610,6 +700,60 @@ fn test_xrange() {
     assert_eq!(reply.ids.len(), 1);
 }
 
+// The smallest id greater than `id`, to resume paging through a stream
+// after it.
+fn next_stream_id(id: &str) -> String {
+    let (ms, seq) = id.split_once('-').unwrap();
+    format!("{}-{}", ms, seq.parse::<u64>().unwrap() + 1)
+}
+
+// The greatest id smaller than `id`, to resume paging backwards before it.
+fn prev_stream_id(id: &str) -> String {
+    let (ms, seq) = id.split_once('-').unwrap();
+    match seq.parse::<u64>().unwrap() {
+        0 => format!("{}-{}", ms.parse::<u64>().unwrap() - 1, u64::MAX),
+        seq => format!("{}-{}", ms, seq - 1),
+    }
+}
+
+#[test]
+fn test_xrange_count_paging() {
+    let ctx = TestContext::new();
+    let mut con = ctx.connection();
+
+    let mut pipe = redis::pipe();
+    for i in 1..=1000 {
+        pipe.xadd("paged", format!("1-{i}"), &[("i", i)]).ignore();
+    }
+    let () = pipe.query(&mut con).unwrap();
+
+    let mut seen = Vec::new();
+    let mut start = "-".to_string();
+    loop {
+        let reply: StreamRangeReply = con.xrange_count("paged", &start, "+", 100).unwrap();
+        if reply.ids.is_empty() {
+            break;
+        }
+        assert_eq!(reply.ids.len(), 100);
+        start = next_stream_id(&reply.ids.last().unwrap().id);
+        seen.extend(reply.ids.into_iter().map(|id| id.get::<u32>("i").unwrap()));
+    }
+    assert_eq!(seen, (1..=1000).collect::<Vec<_>>());
+
+    let mut seen = Vec::new();
+    let mut end = "+".to_string();
+    loop {
+        let reply: StreamRangeReply = con.xrevrange_count("paged", &end, "-", 100).unwrap();
+        if reply.ids.is_empty() {
+            break;
+        }
+        assert_eq!(reply.ids.len(), 100);
+        end = prev_stream_id(&reply.ids.last().unwrap().id);
+        seen.extend(reply.ids.into_iter().map(|id| id.get::<u32>("i").unwrap()));
+    }
+    assert_eq!(seen, (1..=1000).rev().collect::<Vec<_>>());
+}
+
 #[test]
 fn test_xrevrange() {
     // Tests the following commands....
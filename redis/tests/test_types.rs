@@ -33,6 +33,431 @@ fn test_info_dict() {
     assert_eq!(d.get::<String>("key3"), None);
 }
 
+#[test]
+fn test_server_info() {
+    use redis::{FromRedisValue, ServerInfo, ServerRole, Value};
+
+    let info = ServerInfo::from_redis_value(&Value::Data(
+        "# Server\r\nredis_version:7.0.0\r\nuptime_in_seconds:12\r\n\r\n\
+         # Clients\r\nconnected_clients:3\r\n\r\n\
+         # Memory\r\nused_memory:1024\r\n\r\n\
+         # Replication\r\nrole:slave\r\n"
+            .into(),
+    ))
+    .unwrap();
+
+    assert_eq!(info.server_version(), Some("7.0.0"));
+    assert_eq!(info.uptime_in_seconds(), Some(12));
+    assert_eq!(info.connected_clients(), Some(3));
+    assert_eq!(info.used_memory(), Some(1024));
+    assert_eq!(info.role(), Some(ServerRole::Replica));
+    assert_eq!(info.get::<u64>("connected_clients"), Some(3));
+    assert_eq!(info.len(), 5);
+
+    let info = ServerInfo::new("# Clients\nconnected_clients:x\n");
+    assert_eq!(info.connected_clients(), None);
+    assert_eq!(info.server_version(), None);
+    assert_eq!(info.role(), None);
+}
+
+#[test]
+fn test_encoding() {
+    use redis::{Encoding, FromRedisValue, Value};
+
+    let e = Encoding::from_redis_value(&Value::Data("embstr".into()));
+    assert_eq!(e, Ok(Encoding::Embstr));
+
+    let e = Encoding::from_redis_value(&Value::Status("quicklist".into()));
+    assert_eq!(e, Ok(Encoding::Quicklist));
+
+    let e = Encoding::from_redis_value(&Value::Data("ziplist".into()));
+    assert_eq!(e, Ok(Encoding::Ziplist));
+    let e = Encoding::from_redis_value(&Value::Data("listpack".into()));
+    assert_eq!(e, Ok(Encoding::Listpack));
+    assert_eq!(Encoding::Listpack.to_string(), "listpack");
+
+    let e = Encoding::from_redis_value(&Value::Data("json".into()));
+    assert_eq!(e, Ok(Encoding::Json));
+    assert_eq!(Encoding::Json.to_string(), "json");
+
+    let e = Encoding::from_redis_value(&Value::Data("something-new".into()));
+    assert_eq!(e, Ok(Encoding::Unknown("something-new".into())));
+    assert_eq!(e.unwrap().to_string(), "something-new");
+
+    let e = Encoding::from_redis_value(&Value::Status("ReJSON-RL".into()));
+    assert_eq!(
+        e,
+        Ok(Encoding::Module {
+            module_name: "ReJSON".into(),
+            encoding: "RL".into(),
+        })
+    );
+    assert_eq!(e.unwrap().to_string(), "ReJSON-RL");
+
+    let e = Encoding::from_redis_value(&Value::Data("MBbloom--".into()));
+    assert_eq!(
+        e,
+        Ok(Encoding::Module {
+            module_name: "MBbloom".into(),
+            encoding: "-".into(),
+        })
+    );
+
+    let e: Option<Encoding> = FromRedisValue::from_redis_value(&Value::Nil).unwrap();
+    assert_eq!(e, None);
+
+    // As nested in an EXEC reply
+    let e: Vec<Option<Encoding>> = FromRedisValue::from_redis_value(&Value::Bulk(vec![
+        Value::Data("listpack".into()),
+        Value::Nil,
+        Value::Status("intset".into()),
+    ]))
+    .unwrap();
+    assert_eq!(
+        e,
+        vec![Some(Encoding::Listpack), None, Some(Encoding::Intset)]
+    );
+}
+
+#[test]
+fn test_value_display() {
+    use redis::Value;
+
+    assert_eq!(Value::Status("listpack".into()).to_string(), "listpack");
+    assert_eq!(
+        Value::Data(b"hello".to_vec()).to_string(),
+        "<5 bytes: 68 65 6c 6c 6f>"
+    );
+    assert_eq!(Value::Data(vec![]).to_string(), "<0 bytes>");
+    assert_eq!(
+        Value::Data(vec![0xff; 20]).to_string(),
+        format!("<20 bytes: {} ...>", vec!["ff"; 16].join(" "))
+    );
+    assert_eq!(
+        Value::Bulk(vec![Value::Int(1), Value::Nil, Value::Okay]).to_string(),
+        "[1, nil, OK]"
+    );
+    assert_eq!(
+        Value::Map(vec![(Value::Status("a".into()), Value::Int(1))]).to_string(),
+        "{a: 1}"
+    );
+}
+
+#[test]
+fn test_value_accessors() {
+    use redis::Value;
+
+    assert!(Value::Nil.is_nil());
+    assert!(!Value::Bulk(vec![]).is_nil());
+    assert!(Value::Okay.is_okay());
+    assert!(!Value::Status("QUEUED".into()).is_okay());
+
+    let bulk = Value::Bulk(vec![Value::Int(1), Value::Nil]);
+    assert_eq!(bulk.as_bulk(), Some(&[Value::Int(1), Value::Nil][..]));
+    assert_eq!(Value::Set(vec![Value::Int(1)]).as_bulk(), None);
+    assert_eq!(Value::Nil.as_bulk(), None);
+
+    assert_eq!(Value::Int(42).as_integer(), Some(42));
+    assert_eq!(Value::Data(b"42".to_vec()).as_integer(), None);
+}
+
+#[test]
+fn test_key_type() {
+    use redis::{FromRedisValue, KeyType, ToRedisArgs, Value};
+
+    let t: Option<KeyType> =
+        FromRedisValue::from_redis_value(&Value::Status("zset".into())).unwrap();
+    assert_eq!(t, Some(KeyType::ZSet));
+
+    let t: Option<KeyType> =
+        FromRedisValue::from_redis_value(&Value::Status("none".into())).unwrap();
+    assert_eq!(t, None);
+
+    let t: Option<KeyType> =
+        FromRedisValue::from_redis_value(&Value::Status("ReJSON-RL".into())).unwrap();
+    assert_eq!(t, Some(KeyType::Unknown("ReJSON-RL".into())));
+
+    assert_eq!(KeyType::ZSet.to_string(), "zset");
+    assert_eq!(KeyType::Unknown("ReJSON-RL".into()).as_str(), "ReJSON-RL");
+    assert_eq!(KeyType::Hash.to_redis_args(), vec![b"hash".to_vec()]);
+}
+
+#[test]
+fn test_cluster_slot() {
+    use redis::{ClusterNode, ClusterSlot, FromRedisValue, Value};
+
+    let node = |port: i64, id: &str| {
+        Value::Bulk(vec![
+            Value::Data("127.0.0.1".into()),
+            Value::Int(port),
+            Value::Data(id.into()),
+        ])
+    };
+    let v = Value::Bulk(vec![Value::Bulk(vec![
+        Value::Int(0),
+        Value::Int(5460),
+        node(7000, "a"),
+        node(7003, "b"),
+    ])]);
+    let slots: Vec<ClusterSlot> = FromRedisValue::from_redis_value(&v).unwrap();
+    assert_eq!(
+        slots,
+        vec![ClusterSlot {
+            start: 0,
+            end: 5460,
+            primary: ClusterNode {
+                host: "127.0.0.1".into(),
+                port: 7000,
+                id: Some("a".into()),
+            },
+            replicas: vec![ClusterNode {
+                host: "127.0.0.1".into(),
+                port: 7003,
+                id: Some("b".into()),
+            }],
+        }]
+    );
+
+    // Redis 3 does not report node ids
+    let v = Value::Bulk(vec![
+        Value::Int(0),
+        Value::Int(16383),
+        Value::Bulk(vec![Value::Data("127.0.0.1".into()), Value::Int(7000)]),
+    ]);
+    let slot = ClusterSlot::from_redis_value(&v).unwrap();
+    assert_eq!(slot.primary.id, None);
+    assert!(slot.replicas.is_empty());
+
+    assert!(ClusterSlot::from_redis_value(&Value::Bulk(vec![Value::Int(0)])).is_err());
+}
+
+#[test]
+fn test_cluster_shard() {
+    use redis::{ClusterNode, ClusterShard, FromRedisValue, Value};
+
+    let node = |port: i64, id: &str, role: &str| {
+        Value::Map(vec![
+            (Value::Data("id".into()), Value::Data(id.into())),
+            (Value::Data("port".into()), Value::Int(port)),
+            (Value::Data("ip".into()), Value::Data("10.0.0.1".into())),
+            (
+                Value::Data("endpoint".into()),
+                Value::Data("127.0.0.1".into()),
+            ),
+            (Value::Data("role".into()), Value::Data(role.into())),
+            (Value::Data("health".into()), Value::Data("online".into())),
+        ])
+    };
+    let v = Value::Map(vec![
+        (
+            Value::Data("slots".into()),
+            Value::Bulk(vec![
+                Value::Int(0),
+                Value::Int(100),
+                Value::Int(200),
+                Value::Int(300),
+            ]),
+        ),
+        (
+            Value::Data("nodes".into()),
+            Value::Bulk(vec![node(7003, "b", "replica"), node(7000, "a", "master")]),
+        ),
+    ]);
+    let shard = ClusterShard::from_redis_value(&v).unwrap();
+    assert_eq!(shard.slots, vec![(0, 100), (200, 300)]);
+    assert_eq!(
+        shard.primary,
+        Some(ClusterNode {
+            host: "127.0.0.1".into(),
+            port: 7000,
+            id: Some("a".into()),
+        })
+    );
+    assert_eq!(shard.replicas.len(), 1);
+    assert_eq!(shard.replicas[0].port, 7003);
+
+    // A shard without slots, as replied with RESP2
+    let v = Value::Bulk(vec![
+        Value::Data("slots".into()),
+        Value::Bulk(vec![]),
+        Value::Data("nodes".into()),
+        Value::Bulk(vec![]),
+    ]);
+    let shard = ClusterShard::from_redis_value(&v).unwrap();
+    assert!(shard.slots.is_empty());
+    assert_eq!(shard.primary, None);
+}
+
+#[test]
+fn test_function_stats() {
+    use redis::{EngineStats, FromRedisValue, FunctionStats, RunningScript, Value};
+
+    let v = Value::Bulk(vec![
+        Value::Data("running_script".into()),
+        Value::Bulk(vec![
+            Value::Data("name".into()),
+            Value::Data("slow".into()),
+            Value::Data("command".into()),
+            Value::Bulk(vec![
+                Value::Data("fcall".into()),
+                Value::Data("slow".into()),
+                Value::Data("0".into()),
+            ]),
+            Value::Data("duration_ms".into()),
+            Value::Int(1500),
+        ]),
+        Value::Data("engines".into()),
+        Value::Bulk(vec![
+            Value::Data("LUA".into()),
+            Value::Bulk(vec![
+                Value::Data("libraries_count".into()),
+                Value::Int(2),
+                Value::Data("functions_count".into()),
+                Value::Int(5),
+            ]),
+        ]),
+    ]);
+    let stats = FunctionStats::from_redis_value(&v).unwrap();
+    assert_eq!(
+        stats.running_script,
+        Some(RunningScript {
+            name: "slow".into(),
+            command: vec!["fcall".into(), "slow".into(), "0".into()],
+            duration_ms: 1500,
+        })
+    );
+    assert_eq!(
+        stats.engines["LUA"],
+        EngineStats {
+            libraries_count: 2,
+            functions_count: 5,
+        }
+    );
+
+    // Nothing running, as replied with RESP3
+    let v = Value::Map(vec![
+        (Value::Data("running_script".into()), Value::Nil),
+        (Value::Data("engines".into()), Value::Map(vec![])),
+    ]);
+    let stats = FunctionStats::from_redis_value(&v).unwrap();
+    assert_eq!(stats.running_script, None);
+    assert!(stats.engines.is_empty());
+}
+
+#[test]
+fn test_sentinel_info() {
+    use redis::{FromRedisValue, SentinelInfo, SentinelMasterInfo, SentinelSlaveInfo, Value};
+
+    let entry = |fields: &[(&str, &str)]| {
+        Value::Bulk(
+            fields
+                .iter()
+                .flat_map(|(k, v)| {
+                    vec![
+                        Value::Data(k.as_bytes().to_vec()),
+                        Value::Data(v.as_bytes().to_vec()),
+                    ]
+                })
+                .collect(),
+        )
+    };
+
+    let masters: Vec<SentinelMasterInfo> =
+        FromRedisValue::from_redis_value(&Value::Bulk(vec![entry(&[
+            ("name", "mymaster"),
+            ("ip", "127.0.0.1"),
+            ("port", "6379"),
+            ("runid", "953ae6a589449c13ddefaee3538d356d287f509b"),
+            ("flags", "master"),
+            ("num-slaves", "2"),
+            ("num-other-sentinels", "2"),
+            ("quorum", "2"),
+        ])]))
+        .unwrap();
+    assert_eq!(masters.len(), 1);
+    assert_eq!(masters[0].name, "mymaster");
+    assert_eq!(masters[0].ip, "127.0.0.1");
+    assert_eq!(masters[0].port, 6379);
+    assert_eq!(masters[0].flags, "master");
+    assert_eq!(masters[0].num_slaves, 2);
+    assert_eq!(masters[0].num_other_sentinels, 2);
+    assert_eq!(masters[0].quorum, 2);
+    assert_eq!(
+        masters[0].fields.get("runid").map(String::as_str),
+        Some("953ae6a589449c13ddefaee3538d356d287f509b")
+    );
+
+    let slave = SentinelSlaveInfo::from_redis_value(&entry(&[
+        ("name", "127.0.0.1:6380"),
+        ("ip", "127.0.0.1"),
+        ("port", "6380"),
+        ("flags", "slave"),
+        ("master-link-status", "ok"),
+        ("master-host", "127.0.0.1"),
+        ("master-port", "6379"),
+    ]))
+    .unwrap();
+    assert_eq!(slave.name, "127.0.0.1:6380");
+    assert_eq!(slave.port, 6380);
+    assert_eq!(slave.master_link_status, "ok");
+    assert_eq!(slave.master_host, "127.0.0.1");
+    assert_eq!(slave.master_port, 6379);
+
+    let sentinel = SentinelInfo::from_redis_value(&entry(&[
+        ("name", "127.0.0.1:26380"),
+        ("ip", "127.0.0.1"),
+        ("port", "26380"),
+        ("runid", "e0b7e2c1fc1ac0ac2b5e6e1b7b2a1e6e4f6a1c11"),
+        ("flags", "sentinel"),
+    ]))
+    .unwrap();
+    assert_eq!(sentinel.port, 26380);
+    assert_eq!(sentinel.runid, "e0b7e2c1fc1ac0ac2b5e6e1b7b2a1e6e4f6a1c11");
+    assert_eq!(sentinel.flags, "sentinel");
+
+    // Missing and invalid fields are errors
+    assert!(SentinelInfo::from_redis_value(&entry(&[("name", "x")])).is_err());
+    assert!(SentinelInfo::from_redis_value(&entry(&[
+        ("name", "x"),
+        ("ip", "127.0.0.1"),
+        ("port", "not a port"),
+        ("runid", "x"),
+        ("flags", "sentinel"),
+    ]))
+    .is_err());
+}
+
+#[test]
+fn test_client_info() {
+    use redis::{ClientInfo, FromRedisValue, Value};
+
+    let list = "id=3 addr=127.0.0.1:50188 laddr=127.0.0.1:6379 fd=8 name=worker age=12 idle=0 \
+                flags=N db=2 cmd=client|list user=default\n\
+                id=4 addr=127.0.0.1:50190 laddr=127.0.0.1:6379 fd=9 name= age=3 idle=3 \
+                flags=P db=0 cmd=subscribe user=default\n";
+    let clients: Vec<ClientInfo> =
+        FromRedisValue::from_redis_value(&Value::Data(list.into())).unwrap();
+    assert_eq!(clients.len(), 2);
+    assert_eq!(clients[0].id, 3);
+    assert_eq!(clients[0].addr, "127.0.0.1:50188");
+    assert_eq!(clients[0].name, "worker");
+    assert_eq!(clients[0].age, 12);
+    assert_eq!(clients[0].db, 2);
+    assert_eq!(clients[0].cmd, "client|list");
+    assert_eq!(clients[0].fields.get("user"), Some(&"default".to_string()));
+    assert_eq!(clients[1].name, "");
+    assert_eq!(clients[1].flags, "P");
+
+    let client = ClientInfo::from_redis_value(&Value::Data("id=7 addr=x\n".into())).unwrap();
+    assert_eq!(client.id, 7);
+
+    let clients: Vec<ClientInfo> =
+        FromRedisValue::from_redis_value(&Value::Data("".into())).unwrap();
+    assert!(clients.is_empty());
+
+    assert!(ClientInfo::from_redis_value(&Value::Data("addr=x".into())).is_err());
+}
+
 #[test]
 fn test_i32() {
     use redis::{ErrorKind, FromRedisValue, Value};
@@ -123,6 +548,42 @@ fn test_tuple() {
     assert_eq!(v, Ok(((1i32, 2, 3,),)));
 }
 
+#[test]
+fn test_zmpop_reply() {
+    use redis::{ErrorKind, FromRedisValue, Value, ZMPopReply};
+
+    let pair = |member: &str, score: &str| {
+        Value::Bulk(vec![Value::Data(member.into()), Value::Data(score.into())])
+    };
+
+    let v = ZMPopReply::from_redis_value(&Value::Bulk(vec![
+        Value::Data("zset".into()),
+        Value::Bulk(vec![pair("a", "1"), pair("b", "2.5")]),
+    ]));
+    assert_eq!(
+        v,
+        Ok(ZMPopReply {
+            key: "zset".to_string(),
+            members: vec![("a".to_string(), 1.0), ("b".to_string(), 2.5)],
+        })
+    );
+
+    let v: Option<ZMPopReply> = FromRedisValue::from_redis_value(&Value::Nil).unwrap();
+    assert_eq!(v, None);
+
+    // The members are nested pairs, not a flat list
+    let v = ZMPopReply::from_redis_value(&Value::Bulk(vec![
+        Value::Data("zset".into()),
+        Value::Bulk(vec![Value::Data("a".into()), Value::Data("1".into())]),
+    ]));
+    assert_eq!(v.unwrap_err().kind(), ErrorKind::TypeError);
+
+    // Vec<(A, B)> is still only read from flat lists
+    let v: Result<Vec<(String, f64)>, _> =
+        FromRedisValue::from_redis_value(&Value::Bulk(vec![pair("a", "1")]));
+    assert_eq!(v.unwrap_err().kind(), ErrorKind::TypeError);
+}
+
 #[test]
 fn test_hashmap() {
     use fnv::FnvHasher;
@@ -304,3 +765,60 @@ fn test_types_to_redis_args() {
         .to_redis_args()
         .is_empty());
 }
+
+#[test]
+fn test_smart_pointers() {
+    use redis::{FromRedisValue, RedisResult, ToRedisArgs, Value};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    assert_eq!(Box::new("key").to_redis_args(), vec![b"key".to_vec()]);
+    assert_eq!(Arc::new(42).to_redis_args(), vec![b"42".to_vec()]);
+    assert_eq!(
+        Rc::new(vec!["a", "b"]).to_redis_args(),
+        vec![b"a".to_vec(), b"b".to_vec()]
+    );
+    assert!(!Rc::new(vec!["a", "b"]).is_single_arg());
+
+    let key: Arc<str> = Arc::from("key");
+    assert_eq!(key.to_redis_args(), vec![b"key".to_vec()]);
+    let key: Box<str> = "key".into();
+    assert_eq!(key.to_redis_args(), vec![b"key".to_vec()]);
+    let bytes: Rc<[u8]> = Rc::from(&b"bytes"[..]);
+    assert_eq!(bytes.to_redis_args(), vec![b"bytes".to_vec()]);
+
+    let v: RedisResult<Box<i32>> = FromRedisValue::from_redis_value(&Value::Int(42));
+    assert_eq!(v, Ok(Box::new(42)));
+
+    let v: RedisResult<Arc<String>> =
+        FromRedisValue::from_redis_value(&Value::Data(b"value".to_vec()));
+    assert_eq!(v, Ok(Arc::new("value".to_string())));
+
+    let v: RedisResult<Rc<Vec<i32>>> =
+        FromRedisValue::from_redis_value(&Value::Bulk(vec![Value::Int(1), Value::Int(2)]));
+    assert_eq!(v, Ok(Rc::new(vec![1, 2])));
+}
+
+#[test]
+fn test_cluster_redirect() {
+    use redis::parse_redis_value;
+
+    let err = parse_redis_value(b"-MOVED 3999 127.0.0.1:6381\r\n").unwrap_err();
+    assert!(err.is_cluster_error());
+    assert_eq!(
+        err.cluster_redirect(),
+        Some(("127.0.0.1".to_string(), 6381))
+    );
+
+    let err = parse_redis_value(b"-ASK 3999 [::1]:6381\r\n").unwrap_err();
+    assert!(err.is_cluster_error());
+    assert_eq!(err.cluster_redirect(), Some(("::1".to_string(), 6381)));
+
+    let err = parse_redis_value(b"-CLUSTERDOWN The cluster is down\r\n").unwrap_err();
+    assert!(err.is_cluster_error());
+    assert_eq!(err.cluster_redirect(), None);
+
+    let err = parse_redis_value(b"-ERR unknown command\r\n").unwrap_err();
+    assert!(!err.is_cluster_error());
+    assert_eq!(err.cluster_redirect(), None);
+}